@@ -1,6 +1,5 @@
 mod part1;
 mod part2;
-mod program;
 
 fn main() {
     part1::part1();
@@ -1,5 +1,5 @@
 use std::fs::read_to_string;
-use crate::program::Program;
+use intcode::{Computer, Program};
 
 fn substitute(input_str: String) -> String
 {
@@ -17,8 +17,10 @@ pub fn part1()
 
     intcode_str = substitute(intcode_str);
 
-    let mut program = Program::new_from_str(intcode_str);
-    program.run();
+    let program = Program::new_from_str(intcode_str);
+    let mut computer = Computer::new();
+    computer.load_program(&program);
+    computer.run().unwrap();
 
-    println!("Value at address 0: {}", program.get_value(0));
+    println!("Value at address 0: {}", computer.get_value(0));
 }
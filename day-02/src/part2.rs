@@ -1,43 +1,33 @@
 use std::fs::read_to_string;
-use crate::program::Program;
+use intcode::{Program, Solver};
+
+// no sensible noun/verb pair needs more than this, anything longer is stuck in a loop
+const INSTRUCTION_BUDGET: u64 = 100_000;
+
+const TARGET: i64 = 19690720;
 
 struct NounVerb
 {
-    noun: i32,
-    verb: i32
+    noun: i64,
+    verb: i64
 }
 
-fn substitute(input_str: &String, n: i32, v: i32) -> String
+fn find_noun_verb(program: &Program) -> NounVerb
 {
-    let mut intcode: Vec<String> = input_str.split(',').map(|s| s.to_string()).collect();
-    intcode[1] = n.to_string();
-    intcode[2] = v.to_string();
-
-    return intcode.join(",");
-}
+    let solver = Solver::new(program, 0)
+        .with_input(1, 0..=99)
+        .with_input(2, 0..=99)
+        .with_max_instructions(INSTRUCTION_BUDGET);
 
-fn find_noun_verb(initial_memory_str: String) -> NounVerb
-{
-    for n in 0..100
+    match solver.solve(TARGET)
     {
-        for v in 0..100
+        Some(solution) => NounVerb
         {
-            let modified_memory_str = substitute(&initial_memory_str, n, v);
-            let mut program = Program::new_from_str(modified_memory_str);
-            program.run();
-
-            if program.get_value(0) == 19690720
-            {
-                return NounVerb
-                {
-                    noun: n,
-                    verb: v
-                }
-            }
-        }
+            noun: solution.values[0],
+            verb: solution.values[1]
+        },
+        None => panic!("Noun and verb couldn't be found")
     }
-
-    panic!("Noun and verb couldn't be found");
 }
 
 
@@ -46,7 +36,7 @@ pub fn part2()
     let mut intcode_str = read_to_string("input/part1.txt").unwrap();
     intcode_str = intcode_str.trim_end().to_string();
 
-    let noun_verb = find_noun_verb(intcode_str);
+    let noun_verb = find_noun_verb(&Program::new_from_str(intcode_str));
 
     println!("Noun: {}, Verb: {}, Answer: {}",
              noun_verb.noun,
@@ -1,9 +1,24 @@
 mod part1;
 mod part2;
-mod computer;
+
+use std::env;
+use std::process::exit;
 
 fn main()
 {
-    part1::part1();
-    part2::part2();
+    let args: Vec<String> = env::args().collect();
+
+    let mut backend = match intcode::open_backend(&args[1..])
+    {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Usage: {} {}", args[0], intcode::BACKEND_USAGE);
+            exit(1);
+        }
+    };
+
+    // both parts share the backend, part 1 takes the first input
+    part1::part1(&mut backend);
+    part2::part2(&mut backend);
 }
@@ -1,16 +1,31 @@
 use std::fs::read_to_string;
-use crate::computer::Computer;
-use crate::computer::Program;
+use intcode::{Computer, IoBackend, Program, RunState};
 
-pub fn part2()
+pub fn part2(backend: &mut dyn IoBackend)
 {
     let mut intcode_str = read_to_string("input/part1.txt").unwrap();
     intcode_str = intcode_str.trim_end().to_string();
 
     let program = Program::new_from_str(intcode_str);
     let mut computer = Computer::new();
-    computer.load_program(program);
+    computer.load_program(&program);
 
-    println!("Press 5 at the prompt");
-    computer.run();
+    if backend.is_interactive()
+    {
+        println!("Press 5 at the prompt");
+    }
+
+    computer.set_io_backend(backend);
+    let state = computer.run();
+
+    if let Some(e) = computer.take_io_error()
+    {
+        panic!("I/O failed: {}", e);
+    }
+
+    match state.unwrap()
+    {
+        RunState::Halted => (),
+        state => panic!("Diagnostic didn't finish, the computer stopped with {:?}", state)
+    }
 }
@@ -1,6 +1,5 @@
 mod part1;
 mod part2;
-mod computer;
 
 fn main()
 {
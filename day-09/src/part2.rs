@@ -1,5 +1,5 @@
 use std::fs::read_to_string;
-use crate::computer::{Computer, Program, IOQueue};
+use intcode::{Computer, Program, RunState};
 
 pub fn part2()
 {
@@ -9,21 +9,15 @@ pub fn part2()
 
     let mut computer = Computer::new();
     computer.load_program(&program);
-    let computer_input = IOQueue::new();
-    let computer_output = IOQueue::new();
-    computer.set_input(&computer_input);
-    computer.set_output(&computer_output);
 
-    computer_input.push_front("2".to_string());
-    computer.run();
-
-    if !computer.is_halted()
+    let mut outputs = computer.into_outputs(vec![2]);
+    for value in outputs.by_ref()
     {
-        panic!("Computer isn't finished");
+        println!("{}", value.unwrap());
     }
 
-    for s in computer_output
+    if outputs.state() != Some(Ok(RunState::Halted))
     {
-        println!("{}", s);
+        panic!("Computer isn't finished");
     }
 }
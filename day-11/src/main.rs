@@ -1,4 +1,3 @@
-mod computer;
 mod robot;
 mod part1;
 mod part2;
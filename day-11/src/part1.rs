@@ -1,5 +1,5 @@
 use std::fs::read_to_string;
-use crate::computer::Program;
+use intcode::Program;
 use crate::robot::{PaintingRobot, Color};
 
 pub fn part1() {
@@ -8,6 +8,6 @@ pub fn part1() {
     let program = Program::new_from_str(intcode_str);
     let mut robo = PaintingRobot::new(program, Color::Black);
 
-    robo.run();
+    robo.run().unwrap();
     println!("Painted {} cells at least once", robo.get_grid().len())
 }
@@ -1,5 +1,5 @@
 use std::fs::read_to_string;
-use crate::computer::Program;
+use intcode::Program;
 use crate::robot::{PaintingRobot, Color};
 
 pub fn part2() {
@@ -8,6 +8,6 @@ pub fn part2() {
     let program = Program::new_from_str(intcode_str);
     let mut robo = PaintingRobot::new(program, Color::White);
 
-    robo.run();
+    robo.run().unwrap();
     robo.get_grid().show();
 }
@@ -1,4 +1,4 @@
-use crate::computer::{Computer, Program};
+use intcode::{Computer, IntcodeError, Program, RunState};
 
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -207,8 +207,8 @@ impl<'a> PaintingRobot<'a> {
         }
     }
 
-    pub fn run(&mut self) {
-        self.computer.run();
+    pub fn run(&mut self) -> Result<RunState, IntcodeError> {
+        self.computer.run()
     }
 
     pub fn get_grid(&self) -> GridRef {
@@ -1,4 +1,4 @@
-use crate::computer::{Computer, Program};
+use intcode::{Computer, IntcodeError, Program, RunState};
 
 use std::rc::Rc;
 use std::cell::{Cell, RefCell, Ref, RefMut};
@@ -121,11 +121,11 @@ impl<'a> ArcdeCabinet<'a> {
         Self {screen, score, computer, prog}
     }
 
-    pub fn run(&mut self) {
-        self.computer.run();
+    pub fn run(&mut self) -> Result<RunState, IntcodeError> {
+        self.computer.run()
     }
 
-    pub fn step(&mut self) -> bool {
+    pub fn step(&mut self) -> Result<RunState, IntcodeError> {
         self.computer.step()
     }
 
@@ -1,4 +1,4 @@
-use crate::computer::Program;
+use intcode::{Program, RunState};
 use crate::arcade::{ArcdeCabinet, Position};
 
 use gif::{Frame, Encoder, Repeat};
@@ -57,7 +57,7 @@ pub fn beyond() {
     let program = Program::new_from_str(intcode_str);
 
     let mut arcade = ArcdeCabinet::new(program);
-    arcade.run();
+    arcade.run().unwrap();
 
     let dims = get_dims(&arcade);
     let (min_x, min_y, max_x, max_y) = dims;
@@ -82,7 +82,7 @@ pub fn beyond() {
     arcade.insert_coins();
 
     let mut step = 0;
-    while !arcade.step() {
+    while arcade.step().unwrap() != RunState::Halted {
         if step % 500 == 0 {
             let data = build_frame_data(&arcade, dims);
             let mut frame = Frame::default();
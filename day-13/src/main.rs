@@ -1,5 +1,4 @@
 mod arcade;
-mod computer;
 mod part1;
 mod part2;
 mod beyond;
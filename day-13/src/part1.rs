@@ -1,4 +1,4 @@
-use crate::computer::Program;
+use intcode::Program;
 use crate::arcade::{ArcdeCabinet, TileId};
 
 use std::fs::read_to_string;
@@ -9,7 +9,7 @@ pub fn part1() {
     let program = Program::new_from_str(intcode_str);
 
     let mut arcade = ArcdeCabinet::new(program);
-    arcade.run();
+    arcade.run().unwrap();
 
     let num_blocks = arcade.get_screen().values().map(|&t| {if t == TileId::Block {1} else {0}}).collect::<Vec<i64>>().iter().sum::<i64>();
     println!("Number of block tiles: {}", num_blocks);
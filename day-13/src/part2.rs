@@ -1,4 +1,4 @@
-use crate::computer::Program;
+use intcode::Program;
 use crate::arcade::ArcdeCabinet;
 
 use std::fs::read_to_string;
@@ -10,7 +10,7 @@ pub fn part2() {
 
     let mut arcade = ArcdeCabinet::new(program);
     arcade.insert_coins();
-    arcade.run();
+    arcade.run().unwrap();
 
     println!("Final Score: {}", arcade.get_score());
 }
@@ -0,0 +1,162 @@
+// The computer as it was before the instruction cache: every step formats the
+// intcode into a string to decode it, and memory grows on access. Only kept
+// so the benchmark has something to compare the current computer against.
+
+use intcode::Program;
+
+enum Mode
+{
+    Position,
+    Immediate,
+    Relative
+}
+
+impl Mode
+{
+    fn from_char(c: char) -> Self
+    {
+        match c
+        {
+            '0' => Self::Position,
+            '1' => Self::Immediate,
+            '2' => Self::Relative,
+            _ => panic!("Invalid parameter mode {}", c)
+        }
+    }
+}
+
+pub struct BaselineComputer<'a>
+{
+    memory: Vec<i64>,
+    instruction_pointer: usize,
+    relative_base_offset: usize,
+    input_fn: Box<dyn FnMut() -> i64 + 'a>,
+    output_fn: Box<dyn FnMut(i64) + 'a>
+}
+
+impl<'a> BaselineComputer<'a>
+{
+    pub fn new(program: &Program) -> Self
+    {
+        Self
+        {
+            memory: (0..program.len()).map(|a| program.get(a)).collect(),
+            instruction_pointer: 0,
+            relative_base_offset: 0,
+            input_fn: Box::new(|| panic!("No input")),
+            output_fn: Box::new(|_| ())
+        }
+    }
+
+    pub fn set_input<T: FnMut() -> i64 + 'a>(&mut self, input_fn: T)
+    {
+        self.input_fn = Box::new(input_fn);
+    }
+
+    pub fn set_output<T: FnMut(i64) + 'a>(&mut self, output_fn: T)
+    {
+        self.output_fn = Box::new(output_fn);
+    }
+
+    pub fn set_value(&mut self, address: usize, value: i64)
+    {
+        let address = self.check_address(address as i64);
+        self.memory[address] = value;
+    }
+
+    pub fn run(&mut self)
+    {
+        loop
+        {
+            let chars: Vec<_> = format!("{:05}", self.memory[self.instruction_pointer]).chars().collect();
+            let opcode: String = chars[3..].iter().collect();
+            let modes = [Mode::from_char(chars[2]), Mode::from_char(chars[1]), Mode::from_char(chars[0])];
+
+            match opcode.as_ref()
+            {
+                "01" | "02" | "07" | "08" => {
+                    let arg1 = self.read(&modes[0], 1);
+                    let arg2 = self.read(&modes[1], 2);
+                    let result_address = self.address(&modes[2], 3);
+
+                    self.memory[result_address] = match opcode.as_ref()
+                    {
+                        "01" => arg1 + arg2,
+                        "02" => arg1 * arg2,
+                        "07" => (arg1 < arg2) as i64,
+                        _ => (arg1 == arg2) as i64
+                    };
+                    self.instruction_pointer += 4;
+                },
+                "03" => {
+                    let destination_address = self.address(&modes[0], 1);
+                    self.memory[destination_address] = (self.input_fn)();
+                    self.instruction_pointer += 2;
+                },
+                "04" => {
+                    let output = self.read(&modes[0], 1);
+                    (self.output_fn)(output);
+                    self.instruction_pointer += 2;
+                },
+                "05" | "06" => {
+                    let condition = self.read(&modes[0], 1);
+                    let target = self.read(&modes[1], 2);
+
+                    if (condition != 0) == (opcode == "05")
+                    {
+                        self.instruction_pointer = target as usize;
+                    }
+                    else
+                    {
+                        self.instruction_pointer += 3;
+                    }
+                },
+                "09" => {
+                    let adjustment = self.read(&modes[0], 1);
+                    self.relative_base_offset = (self.relative_base_offset as i64 + adjustment) as usize;
+                    self.instruction_pointer += 2;
+                },
+                "99" => return,
+                _ => panic!("Invalid opcode {}", opcode)
+            }
+        }
+    }
+
+    fn check_address(&mut self, address: i64) -> usize
+    {
+        if address < 0
+        {
+            panic!("Negative address {}", address);
+        }
+
+        if (address as usize) >= self.memory.len()
+        {
+            self.memory.resize((address + 1) as usize, 0);
+        }
+
+        address as usize
+    }
+
+    fn address(&mut self, mode: &Mode, offset: usize) -> usize
+    {
+        let parameter = self.memory[self.instruction_pointer + offset];
+
+        match mode
+        {
+            Mode::Position | Mode::Immediate => self.check_address(parameter),
+            Mode::Relative => self.check_address(parameter + self.relative_base_offset as i64)
+        }
+    }
+
+    fn read(&mut self, mode: &Mode, offset: usize) -> i64
+    {
+        match mode
+        {
+            Mode::Immediate => self.memory[self.instruction_pointer + offset],
+            _ => {
+                let address = self.address(mode, offset);
+                self.memory[address]
+            }
+        }
+    }
+}
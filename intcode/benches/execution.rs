@@ -0,0 +1,117 @@
+mod baseline;
+
+use baseline::BaselineComputer;
+use intcode::{Computer, Program, IOQueue};
+
+use std::cell::Cell;
+use std::fs::read_to_string;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 10;
+
+fn load(path: &str) -> Program
+{
+    let intcode_str = read_to_string(format!("{}/../{}", env!("CARGO_MANIFEST_DIR"), path)).unwrap();
+    Program::new_from_str(intcode_str.trim_end().to_string())
+}
+
+fn bench<F: FnMut()>(name: &str, mut f: F)
+{
+    // warm up once so the first iteration doesn't pay for page faults
+    f();
+
+    let mut total = Duration::new(0, 0);
+    for _ in 0..ITERATIONS
+    {
+        let start = Instant::now();
+        f();
+        total += start.elapsed();
+    }
+
+    println!("{}: {:?} per run", name, total / ITERATIONS);
+}
+
+fn boost_sensor(program: &Program)
+{
+    let mut computer = Computer::new();
+    computer.load_program(program);
+    let computer_input = IOQueue::new();
+    let computer_output = IOQueue::new();
+    computer.set_input_queue(&computer_input);
+    computer.set_output_queue(&computer_output);
+
+    computer_input.push_back(2).unwrap();
+    computer.run().unwrap();
+}
+
+// output and input of a player that keeps the paddle under the ball
+fn arcade_player() -> (impl FnMut(i64), impl FnMut() -> i64)
+{
+    let ball_x = Rc::new(Cell::new(0));
+    let paddle_x = Rc::new(Cell::new(0));
+    let ball_x_clone = ball_x.clone();
+    let paddle_x_clone = paddle_x.clone();
+
+    let mut tile = Vec::with_capacity(3);
+    let output = move |o| {
+        tile.push(o);
+        if tile.len() == 3
+        {
+            match tile[2]
+            {
+                3 => paddle_x_clone.set(tile[0]),
+                4 => ball_x_clone.set(tile[0]),
+                _ => ()
+            }
+            tile.clear();
+        }
+    };
+
+    (output, move || (ball_x.get() - paddle_x.get()).signum())
+}
+
+fn arcade(program: &Program)
+{
+    let (output, input) = arcade_player();
+
+    let mut computer = Computer::new();
+    computer.load_program(program);
+    computer.set_value(0, 2);
+    computer.set_output(output);
+    computer.set_input(input);
+
+    computer.run().unwrap();
+}
+
+fn baseline_boost_sensor(program: &Program)
+{
+    let mut computer = BaselineComputer::new(program);
+    computer.set_input(|| 2);
+    computer.run();
+}
+
+fn baseline_arcade(program: &Program)
+{
+    let (output, input) = arcade_player();
+
+    let mut computer = BaselineComputer::new(program);
+    computer.set_value(0, 2);
+    computer.set_output(output);
+    computer.set_input(input);
+
+    computer.run();
+}
+
+fn main()
+{
+    let boost = load("day-09/input/part1.txt");
+    let game = load("day-13/input/input.txt");
+
+    // decoding every instruction from a formatted string, the way it was done before the instruction cache
+    bench("baseline: day-09 BOOST sensor", || baseline_boost_sensor(&boost));
+    bench("baseline: day-13 arcade game", || baseline_arcade(&game));
+
+    bench("day-09 BOOST sensor", || boost_sensor(&boost));
+    bench("day-13 arcade game", || arcade(&game));
+}
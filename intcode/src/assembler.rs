@@ -0,0 +1,392 @@
+use crate::opcode::{Opcode, ParameterMode};
+use crate::program::Program;
+
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssemblerErrorKind
+{
+    UnknownMnemonic(String),
+    InvalidOperand(String),
+    WrongOperandCount {expected: usize, found: usize},
+    MissingDestination,
+    UnexpectedDestination,
+    ImmediateDestination,
+    UndefinedLabel(String),
+    DuplicateLabel(String),
+    AddressMismatch {expected: usize, found: usize}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssemblerError
+{
+    pub kind: AssemblerErrorKind,
+    pub line: usize
+}
+
+impl fmt::Display for AssemblerErrorKind
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::UnknownMnemonic(m) => write!(f, "Unknown mnemonic {}", m),
+            Self::InvalidOperand(o) => write!(f, "Invalid operand {}", o),
+            Self::WrongOperandCount {expected, found} => write!(f, "Expected {} operands, found {}", expected, found),
+            Self::MissingDestination => write!(f, "Instruction needs a destination (-> operand)"),
+            Self::UnexpectedDestination => write!(f, "Instruction doesn't take a destination"),
+            Self::ImmediateDestination => write!(f, "Destination can't be an immediate (#) operand"),
+            Self::UndefinedLabel(l) => write!(f, "Undefined label {}", l),
+            Self::DuplicateLabel(l) => write!(f, "Label {} defined more than once", l),
+            Self::AddressMismatch {expected, found} => write!(f, "Address prefix {} doesn't match the assembled address {}", found, expected)
+        }
+    }
+}
+
+impl fmt::Display for AssemblerError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for AssemblerError {}
+
+enum Value
+{
+    Number(i64),
+    Label(String, i64)
+}
+
+struct Operand
+{
+    mode: ParameterMode,
+    value: Value
+}
+
+enum Statement
+{
+    Instruction(Opcode, Vec<Operand>),
+    Data(Vec<Value>)
+}
+
+impl Statement
+{
+    fn len(&self) -> usize
+    {
+        match self
+        {
+            Self::Instruction(_, operands) => operands.len() + 1,
+            Self::Data(values) => values.len()
+        }
+    }
+}
+
+fn is_label(s: &str) -> bool
+{
+    let mut chars = s.chars();
+
+    match chars.next()
+    {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        _ => false
+    }
+}
+
+fn parse_value(s: &str) -> Result<Value, AssemblerErrorKind>
+{
+    let s = s.trim();
+
+    if let Ok(number) = s.parse::<i64>()
+    {
+        return Ok(Value::Number(number));
+    }
+
+    // label, optionally followed by an offset like `table+2`
+    let (label, offset) = match s.find(['+', '-'])
+    {
+        Some(i) => match s[i..].parse::<i64>()
+        {
+            Ok(offset) => (s[..i].trim_end(), offset),
+            Err(_) => return Err(AssemblerErrorKind::InvalidOperand(s.to_string()))
+        },
+        None => (s, 0)
+    };
+
+    if is_label(label)
+    {
+        return Ok(Value::Label(label.to_string(), offset));
+    }
+    else
+    {
+        return Err(AssemblerErrorKind::InvalidOperand(s.to_string()));
+    }
+}
+
+fn parse_operand(s: &str) -> Result<Operand, AssemblerErrorKind>
+{
+    let s = s.trim();
+
+    if let Some(value) = s.strip_prefix('#')
+    {
+        return Ok(Operand {mode: ParameterMode::Immediate, value: parse_value(value)?});
+    }
+
+    if let Some(inner) = s.strip_prefix('[').and_then(|rest| rest.strip_suffix(']'))
+    {
+        let inner = inner.trim();
+
+        if inner == "rb"
+        {
+            return Ok(Operand {mode: ParameterMode::Relative, value: Value::Number(0)});
+        }
+
+        if inner.starts_with("rb+") || inner.starts_with("rb-")
+        {
+            return match inner[2..].replace(' ', "").parse::<i64>()
+            {
+                Ok(offset) => Ok(Operand {mode: ParameterMode::Relative, value: Value::Number(offset)}),
+                Err(_) => Err(AssemblerErrorKind::InvalidOperand(s.to_string()))
+            };
+        }
+
+        return Ok(Operand {mode: ParameterMode::Position, value: parse_value(inner)?});
+    }
+
+    return Err(AssemblerErrorKind::InvalidOperand(s.to_string()));
+}
+
+fn immediate(value: i64) -> Operand
+{
+    Operand {mode: ParameterMode::Immediate, value: Value::Number(value)}
+}
+
+fn relative(offset: i64) -> Operand
+{
+    Operand {mode: ParameterMode::Relative, value: Value::Number(offset)}
+}
+
+// with_modes fills in the parameter modes of an opcode taken from Opcode::from_mnemonic
+fn with_modes(template: Opcode, operands: &[Operand]) -> Opcode
+{
+    let intcode = operands.iter()
+        .zip([100, 1_000, 10_000].iter())
+        .fold(template.to_intcode(), |intcode, (o, place)| intcode + o.mode.digit() * place);
+
+    return Opcode::from_intcode(intcode).unwrap();
+}
+
+fn check_count(expected: usize, found: usize) -> Result<(), AssemblerErrorKind>
+{
+    if expected != found
+    {
+        return Err(AssemblerErrorKind::WrongOperandCount {expected, found});
+    }
+
+    return Ok(());
+}
+
+// parse_statement turns one line (without labels and comments) into the
+// statements it assembles to, `address` is where the first one will be placed
+fn parse_statement(text: &str, address: usize) -> Result<Vec<Statement>, AssemblerErrorKind>
+{
+    let (mnemonic, rest) = match text.find(char::is_whitespace)
+    {
+        Some(i) => (&text[..i], text[i..].trim()),
+        None => (text, "")
+    };
+
+    if mnemonic.eq_ignore_ascii_case(".data")
+    {
+        if rest.is_empty()
+        {
+            return Ok(vec![Statement::Data(vec![])]);
+        }
+
+        let values = rest.split(',').map(parse_value).collect::<Result<Vec<_>, _>>()?;
+        return Ok(vec![Statement::Data(values)]);
+    }
+
+    let mut parts = rest.splitn(2, "->");
+    let sources = match parts.next().map(str::trim)
+    {
+        Some("") | None => vec![],
+        Some(s) => s.split(',').map(parse_operand).collect::<Result<Vec<_>, _>>()?
+    };
+    let destination = match parts.next()
+    {
+        Some(d) => Some(parse_operand(d)?),
+        None => None
+    };
+
+    // Relative base helpers. A call pushes the return address on a stack
+    // growing up from rb: it moves rb up by one and stores the address at
+    // [rb+0], a return moves rb back down and jumps to it.
+    let jump = Opcode::from_mnemonic("JZ").unwrap();
+    let adjust = Opcode::from_mnemonic("ARB").unwrap();
+    match mnemonic.to_ascii_uppercase().as_ref()
+    {
+        "JMP" | "CALL" | "RET" if destination.is_some() => return Err(AssemblerErrorKind::UnexpectedDestination),
+        "JMP" => {
+            check_count(1, sources.len())?;
+            let operands = vec![immediate(0), sources.into_iter().next().unwrap()];
+            return Ok(vec![Statement::Instruction(with_modes(jump, &operands), operands)]);
+        },
+        "CALL" => {
+            check_count(1, sources.len())?;
+            let push = vec![immediate(1)];
+            let store = vec![immediate(address as i64 + 9), immediate(0), relative(0)];
+            let target = match sources.into_iter().next().unwrap()
+            {
+                // rb has already moved when the jump reads its target
+                Operand {mode: ParameterMode::Relative, value: Value::Number(offset)} => relative(offset - 1),
+                target => target
+            };
+            let operands = vec![immediate(0), target];
+            return Ok(vec![
+                Statement::Instruction(with_modes(adjust, &push), push),
+                Statement::Instruction(with_modes(Opcode::from_mnemonic("ADD").unwrap(), &store), store),
+                Statement::Instruction(with_modes(jump, &operands), operands)
+            ]);
+        },
+        "RET" => {
+            check_count(0, sources.len())?;
+            let pop = vec![immediate(-1)];
+            let operands = vec![immediate(0), relative(1)];
+            return Ok(vec![
+                Statement::Instruction(with_modes(adjust, &pop), pop),
+                Statement::Instruction(with_modes(jump, &operands), operands)
+            ]);
+        },
+        _ => ()
+    }
+
+    let template = match Opcode::from_mnemonic(mnemonic)
+    {
+        Some(o) => o,
+        None => return Err(AssemblerErrorKind::UnknownMnemonic(mnemonic.to_string()))
+    };
+
+    let writes = template.writes_last_parameter();
+    check_count(template.parameter_modes().len() - (writes as usize), sources.len())?;
+
+    let mut operands = sources;
+    match (writes, destination)
+    {
+        // nothing can be written to an immediate, it would only fail at runtime
+        (true, Some(d)) if d.mode == ParameterMode::Immediate => return Err(AssemblerErrorKind::ImmediateDestination),
+        (true, Some(d)) => operands.push(d),
+        (true, None) => return Err(AssemblerErrorKind::MissingDestination),
+        (false, Some(_)) => return Err(AssemblerErrorKind::UnexpectedDestination),
+        (false, None) => ()
+    }
+
+    return Ok(vec![Statement::Instruction(with_modes(template, &operands), operands)]);
+}
+
+fn resolve(value: &Value, labels: &HashMap<String, usize>) -> Result<i64, AssemblerErrorKind>
+{
+    match value
+    {
+        Value::Number(n) => Ok(*n),
+        Value::Label(label, offset) => match labels.get(label)
+        {
+            Some(&address) => Ok(address as i64 + offset),
+            None => Err(AssemblerErrorKind::UndefinedLabel(label.clone()))
+        }
+    }
+}
+
+// Source is one statement per line, using the same notation as the disassembler:
+//
+//     start:  IN -> [value]        ; labels end with ':', comments start with ';'
+//             MUL [value], #2 -> [rb+1]
+//             CALL #print          ; JMP, CALL and RET are relative base helpers
+//             HALT
+//     value:  .data 0
+//
+// A numeric prefix like `0012:` is checked against the address being assembled.
+// CALL and RET keep return addresses on a stack at rb, so calls nest as long
+// as rb points at free memory first (ARB #stack) and a function moves rb past
+// anything it keeps at [rb+n] before it calls another one.
+pub fn assemble(source: &str) -> Result<Program, AssemblerError>
+{
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut address = 0;
+
+    for (line_index, line) in source.lines().enumerate()
+    {
+        let error = |kind| AssemblerError {kind, line: line_index + 1};
+
+        let mut text = match line.find(';')
+        {
+            Some(i) => line[..i].trim(),
+            None => line.trim()
+        };
+
+        while let Some(colon) = text.find(':')
+        {
+            let name = text[..colon].trim();
+
+            if let Ok(found) = name.parse::<usize>()
+            {
+                if found != address
+                {
+                    return Err(error(AssemblerErrorKind::AddressMismatch {expected: address, found}));
+                }
+            }
+            else if is_label(name)
+            {
+                if labels.insert(name.to_string(), address).is_some()
+                {
+                    return Err(error(AssemblerErrorKind::DuplicateLabel(name.to_string())));
+                }
+            }
+            else
+            {
+                break;
+            }
+
+            text = text[colon + 1..].trim();
+        }
+
+        if text.is_empty()
+        {
+            continue;
+        }
+
+        for statement in parse_statement(text, address).map_err(error)?
+        {
+            address += statement.len();
+            statements.push((line_index + 1, statement));
+        }
+    }
+
+    let mut memory = Vec::with_capacity(address);
+    for (line, statement) in statements
+    {
+        let error = |kind| AssemblerError {kind, line};
+
+        match statement
+        {
+            Statement::Instruction(opcode, operands) => {
+                memory.push(opcode.to_intcode());
+                for operand in operands
+                {
+                    memory.push(resolve(&operand.value, &labels).map_err(error)?);
+                }
+            },
+            Statement::Data(values) => {
+                for value in values
+                {
+                    memory.push(resolve(&value, &labels).map_err(error)?);
+                }
+            }
+        }
+    }
+
+    return Ok(Program::from(memory));
+}
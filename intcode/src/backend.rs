@@ -0,0 +1,288 @@
+use crate::word::Word;
+
+use std::fs::File;
+use std::io::{self, stdin, stdout, BufRead, BufReader, Write};
+use std::path::Path;
+
+#[cfg(unix)]
+use std::os::unix::fs::FileTypeExt;
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+pub const BACKEND_USAGE: &str = "[stdin | file <input file> [<output file>] | pipe <input fifo> <output fifo> | socket <path> | connect <path>]";
+
+// Where a computer's input comes from and where its output goes, see
+// Computer::set_io_backend. All the backends here exchange one decimal
+// value per line, of whatever Word the computer has.
+pub trait IoBackend<W = i64>
+{
+    // None when the input has run out, the computer then waits for input
+    fn read_value(&mut self) -> io::Result<Option<W>>;
+
+    fn write_value(&mut self, value: W) -> io::Result<()>;
+
+    // whether someone is typing the input
+    fn is_interactive(&self) -> bool
+    {
+        return false;
+    }
+}
+
+impl<W, B: IoBackend<W> + ?Sized> IoBackend<W> for &mut B
+{
+    fn read_value(&mut self) -> io::Result<Option<W>>
+    {
+        return (**self).read_value();
+    }
+
+    fn write_value(&mut self, value: W) -> io::Result<()>
+    {
+        return (**self).write_value(value);
+    }
+
+    fn is_interactive(&self) -> bool
+    {
+        return (**self).is_interactive();
+    }
+}
+
+impl<W, B: IoBackend<W> + ?Sized> IoBackend<W> for Box<B>
+{
+    fn read_value(&mut self) -> io::Result<Option<W>>
+    {
+        return (**self).read_value();
+    }
+
+    fn write_value(&mut self, value: W) -> io::Result<()>
+    {
+        return (**self).write_value(value);
+    }
+
+    fn is_interactive(&self) -> bool
+    {
+        return (**self).is_interactive();
+    }
+}
+
+fn parse_value<W: Word>(line: &str) -> io::Result<W>
+{
+    return line.trim().parse::<W>()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("Not a number: {}", line.trim())));
+}
+
+// Newline-delimited values over any reader and writer, blank lines are
+// skipped. Every value is flushed as soon as it's written so whoever is on
+// the other end of a pipe or socket sees it.
+struct LineIo
+{
+    reader: Box<dyn BufRead>,
+    writer: Box<dyn Write>
+}
+
+impl LineIo
+{
+    fn read_value<W: Word>(&mut self) -> io::Result<Option<W>>
+    {
+        let mut line = String::new();
+
+        loop
+        {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0
+            {
+                return Ok(None);
+            }
+
+            if !line.trim().is_empty()
+            {
+                return parse_value(&line).map(Some);
+            }
+        }
+    }
+
+    fn write_value<W: Word>(&mut self, value: W) -> io::Result<()>
+    {
+        writeln!(self.writer, "{}", value)?;
+        return self.writer.flush();
+    }
+}
+
+// Asks for each input at a prompt and prints outputs, the same as
+// Computer::set_input_stdin and set_output_stdout.
+#[derive(Default)]
+pub struct StdinBackend;
+
+impl StdinBackend
+{
+    pub fn new() -> Self
+    {
+        Self
+    }
+}
+
+impl<W: Word> IoBackend<W> for StdinBackend
+{
+    fn read_value(&mut self) -> io::Result<Option<W>>
+    {
+        print!("Input Requested: ");
+        stdout().flush()?;
+
+        let mut input = String::new();
+        if stdin().read_line(&mut input)? == 0
+        {
+            return Ok(None);
+        }
+
+        return parse_value(&input).map(Some);
+    }
+
+    fn write_value(&mut self, value: W) -> io::Result<()>
+    {
+        println!("{}", value);
+        return Ok(());
+    }
+
+    fn is_interactive(&self) -> bool
+    {
+        return true;
+    }
+}
+
+// Reads inputs from a file and writes outputs to another file, or to stdout
+// when no output file is given.
+pub struct FileBackend(LineIo);
+
+impl FileBackend
+{
+    pub fn open<P: AsRef<Path>>(input: P, output: Option<P>) -> io::Result<Self>
+    {
+        let writer: Box<dyn Write> = match output
+        {
+            Some(path) => Box::new(File::create(path)?),
+            None => Box::new(stdout())
+        };
+
+        return Ok(Self(LineIo {reader: Box::new(BufReader::new(File::open(input)?)), writer}));
+    }
+}
+
+impl<W: Word> IoBackend<W> for FileBackend
+{
+    fn read_value(&mut self) -> io::Result<Option<W>>
+    {
+        return self.0.read_value();
+    }
+
+    fn write_value(&mut self, value: W) -> io::Result<()>
+    {
+        return self.0.write_value(value);
+    }
+}
+
+// Talks to another process through two named pipes made with mkfifo. Opening
+// a pipe blocks until the other end is opened too, the input pipe is opened
+// first so the other process has to open it for writing before it opens the
+// output pipe for reading.
+#[cfg(unix)]
+pub struct PipeBackend(LineIo);
+
+#[cfg(unix)]
+impl PipeBackend
+{
+    pub fn open<P: AsRef<Path>>(input: P, output: P) -> io::Result<Self>
+    {
+        for path in [input.as_ref(), output.as_ref()]
+        {
+            if !path.metadata()?.file_type().is_fifo()
+            {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} isn't a named pipe", path.display())));
+            }
+        }
+
+        let reader = BufReader::new(File::open(input)?);
+        let writer = File::options().write(true).open(output)?;
+
+        return Ok(Self(LineIo {reader: Box::new(reader), writer: Box::new(writer)}));
+    }
+}
+
+#[cfg(unix)]
+impl<W: Word> IoBackend<W> for PipeBackend
+{
+    fn read_value(&mut self) -> io::Result<Option<W>>
+    {
+        return self.0.read_value();
+    }
+
+    fn write_value(&mut self, value: W) -> io::Result<()>
+    {
+        return self.0.write_value(value);
+    }
+}
+
+// Input and output both go over one Unix-domain socket connection.
+#[cfg(unix)]
+pub struct UnixSocketBackend(LineIo);
+
+#[cfg(unix)]
+impl UnixSocketBackend
+{
+    // Creates the socket and waits for one client to connect. The socket file
+    // is removed again once the client is connected.
+    pub fn listen<P: AsRef<Path>>(path: P) -> io::Result<Self>
+    {
+        let listener = UnixListener::bind(&path)?;
+        let accepted = listener.accept();
+        let _ = std::fs::remove_file(&path);
+
+        return Self::from_stream(accepted?.0);
+    }
+
+    // connects to a socket some other process is listening on
+    pub fn connect<P: AsRef<Path>>(path: P) -> io::Result<Self>
+    {
+        return Self::from_stream(UnixStream::connect(path)?);
+    }
+
+    fn from_stream(stream: UnixStream) -> io::Result<Self>
+    {
+        let reader = BufReader::new(stream.try_clone()?);
+
+        return Ok(Self(LineIo {reader: Box::new(reader), writer: Box::new(stream)}));
+    }
+}
+
+#[cfg(unix)]
+impl<W: Word> IoBackend<W> for UnixSocketBackend
+{
+    fn read_value(&mut self) -> io::Result<Option<W>>
+    {
+        return self.0.read_value();
+    }
+
+    fn write_value(&mut self, value: W) -> io::Result<()>
+    {
+        return self.0.write_value(value);
+    }
+}
+
+// Opens the backend named by command line arguments, see BACKEND_USAGE. No
+// arguments means stdin.
+pub fn open_backend<W: Word>(args: &[String]) -> io::Result<Box<dyn IoBackend<W>>>
+{
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+
+    return match args[..]
+    {
+        [] | ["stdin"] => Ok(Box::new(StdinBackend::new())),
+        ["file", input] => Ok(Box::new(FileBackend::open(input, None)?)),
+        ["file", input, output] => Ok(Box::new(FileBackend::open(input, Some(output))?)),
+        #[cfg(unix)]
+        ["pipe", input, output] => Ok(Box::new(PipeBackend::open(input, output)?)),
+        #[cfg(unix)]
+        ["socket", path] => Ok(Box::new(UnixSocketBackend::listen(path)?)),
+        #[cfg(unix)]
+        ["connect", path] => Ok(Box::new(UnixSocketBackend::connect(path)?)),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Unknown I/O backend: {}", args.join(" "))))
+    };
+}
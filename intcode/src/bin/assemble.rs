@@ -0,0 +1,27 @@
+use intcode::assemble;
+
+use std::env;
+use std::fs::read_to_string;
+use std::process::exit;
+
+fn main()
+{
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() != 2
+    {
+        eprintln!("Usage: {} <assembly file>", args[0]);
+        exit(1);
+    }
+
+    let source = read_to_string(&args[1]).unwrap();
+
+    match assemble(&source)
+    {
+        Ok(program) => println!("{}", program),
+        Err(e) => {
+            eprintln!("{}: {}", args[1], e);
+            exit(1);
+        }
+    }
+}
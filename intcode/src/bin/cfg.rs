@@ -0,0 +1,38 @@
+use intcode::{ControlFlowGraph, Program};
+
+use std::env;
+use std::fs::{read_to_string, File};
+use std::io::BufReader;
+use std::process::exit;
+
+fn main()
+{
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2 || args.len() > 3
+    {
+        eprintln!("Usage: {} <intcode file> [trace file]", args[0]);
+        eprintln!("Prints the control-flow graph as Graphviz source, a trace fills in computed jumps.");
+        exit(1);
+    }
+
+    let mut intcode_str = read_to_string(&args[1]).unwrap();
+    intcode_str = intcode_str.trim_end().to_string();
+    let program = Program::new_from_str(intcode_str);
+
+    let mut graph = ControlFlowGraph::build(&program);
+
+    if let Some(trace) = args.get(2)
+    {
+        let jumps = graph.add_trace(BufReader::new(File::open(trace).unwrap())).unwrap();
+        eprintln!("{} computed jumps found in the trace", jumps);
+    }
+
+    let unresolved = graph.unresolved();
+    if !unresolved.is_empty()
+    {
+        eprintln!("{} blocks end in an unresolved jump", unresolved.len());
+    }
+
+    print!("{}", graph.to_dot());
+}
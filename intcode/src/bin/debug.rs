@@ -0,0 +1,45 @@
+use intcode::{Computer, Debugger, Program, Word};
+
+use std::env;
+use std::fs::read_to_string;
+use std::io::{stdin, stdout};
+use std::process::exit;
+
+#[cfg(feature = "bigint")]
+const WORDS: &str = "i64 | i128 | bigint";
+#[cfg(not(feature = "bigint"))]
+const WORDS: &str = "i64 | i128";
+
+fn debug<W: Word>(computer: Computer<W>, program: &Program)
+{
+    let mut debugger = Debugger::with_program(computer, program);
+    debugger.repl(stdin().lock(), stdout()).unwrap();
+}
+
+fn main()
+{
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() != 2 && args.len() != 3
+    {
+        eprintln!("Usage: {} <intcode file> [{}]", args[0], WORDS);
+        exit(1);
+    }
+
+    let mut intcode_str = read_to_string(&args[1]).unwrap();
+    intcode_str = intcode_str.trim_end().to_string();
+    let program = Program::new_from_str(intcode_str);
+
+    // the values the program computes with, memory still starts out as i64
+    match args.get(2).map(|w| w.as_str())
+    {
+        None | Some("i64") => debug(Computer::new(), &program),
+        Some("i128") => debug(Computer::<i128>::default(), &program),
+        #[cfg(feature = "bigint")]
+        Some("bigint") => debug(Computer::<num_bigint::BigInt>::default(), &program),
+        Some(other) => {
+            eprintln!("Unknown word {}, expected one of {}", other, WORDS);
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use intcode::{disassemble, Program};
+
+use std::env;
+use std::fs::read_to_string;
+use std::process::exit;
+
+fn main()
+{
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() != 2
+    {
+        eprintln!("Usage: {} <intcode file>", args[0]);
+        exit(1);
+    }
+
+    let mut intcode_str = read_to_string(&args[1]).unwrap();
+    intcode_str = intcode_str.trim_end().to_string();
+    let program = Program::new_from_str(intcode_str);
+
+    print!("{}", disassemble(&program));
+}
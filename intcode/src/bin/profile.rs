@@ -0,0 +1,55 @@
+use intcode::{Computer, Profiler, Program, RunState};
+
+use std::env;
+use std::fs::read_to_string;
+use std::process::exit;
+
+fn main()
+{
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2
+    {
+        eprintln!("Usage: {} <intcode file> [input]...", args[0]);
+        eprintln!("The last input is repeated whenever the program asks for more.");
+        exit(1);
+    }
+
+    let inputs: Vec<i64> = args[2..].iter().map(|a| a.parse::<i64>().unwrap()).collect();
+
+    let mut intcode_str = read_to_string(&args[1]).unwrap();
+    intcode_str = intcode_str.trim_end().to_string();
+    let program = Program::new_from_str(intcode_str);
+
+    let mut next_input = 0;
+    let mut outputs = 0;
+
+    let mut computer = Computer::new();
+    computer.load_program(&program);
+
+    // without inputs, asking for one just ends the run
+    if !inputs.is_empty()
+    {
+        computer.set_input(|| {
+            let input = inputs[next_input.min(inputs.len() - 1)];
+            next_input += 1;
+            input
+        });
+    }
+
+    computer.set_output(|_| outputs += 1);
+    computer.set_profiler(Profiler::new());
+
+    match computer.run()
+    {
+        Ok(RunState::Halted) => (),
+        Ok(_) => eprintln!("stopped waiting for input at {:04}", computer.instruction_pointer()),
+        Err(e) => eprintln!("error: {}", e)
+    }
+
+    let profiler = computer.take_profiler().unwrap();
+    drop(computer);
+
+    println!("outputs produced: {}", outputs);
+    print!("{}", profiler);
+}
@@ -0,0 +1,32 @@
+use intcode::{IsaProfile, Program};
+
+use std::env;
+use std::fs::read_to_string;
+use std::process::exit;
+
+fn main()
+{
+    let args: Vec<String> = env::args().collect();
+
+    let profile = match args.get(2).map(|name| IsaProfile::from_name(name))
+    {
+        Some(Some(profile)) if args.len() == 3 => profile,
+        _ => {
+            eprintln!("Usage: {} <intcode file> <day2|day5|day9>", args[0]);
+            exit(1);
+        }
+    };
+
+    let mut intcode_str = read_to_string(&args[1]).unwrap();
+    intcode_str = intcode_str.trim_end().to_string();
+    let program = Program::new_from_str(intcode_str);
+
+    match profile.validate(&program)
+    {
+        Ok(()) => println!("valid for the {} instruction set", profile),
+        Err(e) => {
+            println!("{}", e);
+            exit(2);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use crate::disassembler::{find_code_from, Instruction};
+use crate::program::Program;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::{self, BufRead};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EdgeKind
+{
+    // into the next block, including a conditional jump that isn't taken
+    FallThrough,
+    // jump with an immediate target
+    Jump,
+    // jump with a computed target, seen in an execution trace
+    Traced
+}
+
+#[derive(Debug, Clone)]
+pub struct Block
+{
+    pub start: usize,
+    // address of the last instruction
+    pub end: usize,
+    instructions: Vec<Instruction>
+}
+
+impl Block
+{
+    pub fn len(&self) -> usize
+    {
+        return self.instructions.len();
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        return self.instructions.is_empty();
+    }
+
+    fn last(&self) -> &Instruction
+    {
+        return self.instructions.last().unwrap();
+    }
+
+    // ends in a jump whose target is only known at run time
+    pub fn has_dynamic_jump(&self) -> bool
+    {
+        return self.last().is_jump() && self.last().static_jump_target().is_none();
+    }
+}
+
+// Static control-flow graph. Code is found the same way the disassembler finds
+// it and split into basic blocks at jump targets and after jumps. Jumps to
+// computed addresses stay unresolved until they're filled in from a trace.
+pub struct ControlFlowGraph
+{
+    program: Program,
+    traced_jumps: BTreeSet<(usize, usize)>,
+    blocks: BTreeMap<usize, Block>,
+    edges: BTreeSet<(usize, usize, EdgeKind)>
+}
+
+impl ControlFlowGraph
+{
+    pub fn build(program: &Program) -> Self
+    {
+        let mut graph = Self
+        {
+            program: program.clone(),
+            traced_jumps: BTreeSet::new(),
+            blocks: BTreeMap::new(),
+            edges: BTreeSet::new()
+        };
+
+        graph.analyse();
+        return graph;
+    }
+
+    pub fn blocks(&self) -> impl Iterator<Item = &Block>
+    {
+        self.blocks.values()
+    }
+
+    // (from block, to block, kind)
+    pub fn edges(&self) -> impl Iterator<Item = &(usize, usize, EdgeKind)>
+    {
+        self.edges.iter()
+    }
+
+    // blocks ending in a computed jump that no trace has resolved yet
+    pub fn unresolved(&self) -> Vec<usize>
+    {
+        return self.blocks.values()
+            .filter(|b| b.has_dynamic_jump() && !self.traced_jumps.iter().any(|&(from, _)| from == b.end))
+            .map(|b| b.start)
+            .collect();
+    }
+
+    // Records a computed jump from the instruction at `from` to `to`, code
+    // only reachable through it is picked up as well.
+    pub fn add_jump(&mut self, from: usize, to: usize)
+    {
+        if self.traced_jumps.insert((from, to))
+        {
+            self.analyse();
+        }
+    }
+
+    // Fills in computed jumps from a trace written by Tracer, returns the
+    // number of jumps found. Traces limited to an address range still work,
+    // only consecutive steps are taken as a jump.
+    pub fn add_trace<R: BufRead>(&mut self, trace: R) -> io::Result<usize>
+    {
+        let mut previous: Option<(u64, usize)> = None;
+        let mut jumps = BTreeSet::new();
+
+        for line in trace.lines()
+        {
+            let line = line?;
+            let (step, ip) = match (json_number(&line, "step"), json_number(&line, "ip"))
+            {
+                (Some(step), Some(ip)) => (step, ip as usize),
+                _ => continue
+            };
+
+            if let Some((previous_step, previous_ip)) = previous
+            {
+                if step == previous_step + 1
+                {
+                    if let Some(instruction) = Instruction::decode(&self.program, previous_ip)
+                    {
+                        if instruction.is_jump() && instruction.static_jump_target().is_none() && ip != instruction.next_address()
+                        {
+                            jumps.insert((previous_ip, ip));
+                        }
+                    }
+                }
+            }
+
+            previous = Some((step, ip));
+        }
+
+        let found = jumps.len();
+        let before = self.traced_jumps.len();
+        self.traced_jumps.extend(jumps);
+
+        if self.traced_jumps.len() != before
+        {
+            self.analyse();
+        }
+
+        return Ok(found);
+    }
+
+    fn analyse(&mut self)
+    {
+        let mut entries = vec![0];
+        entries.extend(self.traced_jumps.iter().map(|&(_, to)| to));
+        let code = find_code_from(&self.program, &entries);
+
+        let mut leaders: BTreeSet<usize> = entries.into_iter().collect();
+        for instruction in code.values()
+        {
+            if instruction.is_jump() || !instruction.falls_through()
+            {
+                leaders.insert(instruction.next_address());
+            }
+
+            if let Some(target) = instruction.static_jump_target()
+            {
+                leaders.insert(target);
+            }
+        }
+
+        self.blocks.clear();
+        let mut current: Option<Block> = None;
+
+        for (&address, instruction) in &code
+        {
+            let continues = match &current
+            {
+                Some(block) => block.last().next_address() == address && block.last().falls_through() &&
+                               !block.last().is_jump() && !leaders.contains(&address),
+                None => false
+            };
+
+            if !continues
+            {
+                if let Some(block) = current.take()
+                {
+                    self.blocks.insert(block.start, block);
+                }
+
+                current = Some(Block {start: address, end: address, instructions: vec![]});
+            }
+
+            let block = current.as_mut().unwrap();
+            block.end = address;
+            block.instructions.push(instruction.clone());
+        }
+
+        if let Some(block) = current
+        {
+            self.blocks.insert(block.start, block);
+        }
+
+        let block_of: BTreeMap<usize, usize> = self.blocks.values().map(|b| (b.end, b.start)).collect();
+
+        self.edges.clear();
+        for block in self.blocks.values()
+        {
+            let last = block.last();
+
+            if last.falls_through() && self.blocks.contains_key(&last.next_address())
+            {
+                self.edges.insert((block.start, last.next_address(), EdgeKind::FallThrough));
+            }
+
+            if let Some(target) = last.static_jump_target()
+            {
+                if self.blocks.contains_key(&target)
+                {
+                    self.edges.insert((block.start, target, EdgeKind::Jump));
+                }
+            }
+        }
+
+        for &(from, to) in &self.traced_jumps
+        {
+            if let (Some(&block), true) = (block_of.get(&from), self.blocks.contains_key(&to))
+            {
+                self.edges.insert((block, to, EdgeKind::Traced));
+            }
+        }
+    }
+
+    // Graphviz source, computed jumps without a known target point at a "?" node.
+    pub fn to_dot(&self) -> String
+    {
+        let mut dot = String::new();
+
+        writeln!(dot, "digraph intcode {{").unwrap();
+        writeln!(dot, "    node [shape=box, fontname=\"monospace\"];").unwrap();
+
+        for block in self.blocks.values()
+        {
+            let label: String = block.instructions.iter()
+                .map(|i| format!("{:04}: {}\\l", i.address, i).replace('"', "\\\""))
+                .collect();
+            writeln!(dot, "    b{} [label=\"{}\"];", block.start, label).unwrap();
+        }
+
+        for &(from, to, kind) in &self.edges
+        {
+            let style = match kind
+            {
+                EdgeKind::FallThrough => "",
+                EdgeKind::Jump => " [color=blue]",
+                EdgeKind::Traced => " [color=blue, style=dashed, label=\"traced\"]"
+            };
+            writeln!(dot, "    b{} -> b{}{};", from, to, style).unwrap();
+        }
+
+        for start in self.unresolved()
+        {
+            writeln!(dot, "    unresolved{} [shape=plaintext, label=\"?\"];", start).unwrap();
+            writeln!(dot, "    b{} -> unresolved{} [color=red, style=dotted];", start, start).unwrap();
+        }
+
+        writeln!(dot, "}}").unwrap();
+
+        return dot;
+    }
+}
+
+// unsigned number field of a single-line JSON object, enough for Tracer output
+fn json_number(line: &str, key: &str) -> Option<u64>
+{
+    let start = line.find(&format!("\"{}\":", key))? + key.len() + 3;
+    let digits: String = line[start..].chars().take_while(|c| c.is_ascii_digit()).collect();
+
+    return digits.parse().ok();
+}
@@ -0,0 +1,108 @@
+use crate::computer::{Computer, RunState};
+use crate::error::IntcodeError;
+use crate::program::{Program, DEFAULT_MEMORY_LIMIT};
+use crate::word::Word;
+
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+// A computer that can be moved to another thread. Input and output go through
+// mpsc channels, so machines on different threads can be wired together.
+// Tracing, history and the other instrumentation of Computer aren't available.
+pub struct ChannelComputer<W = i64>
+{
+    memory: Program<W>,
+    instruction_pointer: usize,
+    relative_base_offset: usize,
+    memory_limit: usize,
+    input: Receiver<W>,
+    output: Sender<W>
+}
+
+impl<W: Word + Send> ChannelComputer<W>
+{
+    pub fn new(program: &Program, input: Receiver<W>, output: Sender<W>) -> Self
+    {
+        Self
+        {
+            memory: program.widen(),
+            instruction_pointer: 0,
+            relative_base_offset: 0,
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+            input,
+            output
+        }
+    }
+
+    pub fn set_memory_limit(&mut self, limit: usize)
+    {
+        self.memory_limit = limit;
+    }
+
+    // Blocks while waiting for input. Only returns AwaitingInput once every
+    // sender for the input channel has been dropped.
+    pub fn run(&mut self) -> Result<RunState<W>, IntcodeError>
+    {
+        return self.execute(true);
+    }
+
+    // runs until it needs input that hasn't arrived yet
+    pub fn try_run(&mut self) -> Result<RunState<W>, IntcodeError>
+    {
+        return self.execute(false);
+    }
+
+    // Runs the computer on its own thread. Its output sender is dropped when
+    // it stops, so whoever reads the output sees the channel close.
+    pub fn spawn(mut self) -> JoinHandle<Result<RunState<W>, IntcodeError>>
+    {
+        thread::spawn(move || self.run())
+    }
+
+    pub fn instruction_pointer(&self) -> usize
+    {
+        return self.instruction_pointer;
+    }
+
+    pub fn relative_base_offset(&self) -> usize
+    {
+        return self.relative_base_offset;
+    }
+
+    pub fn get_value(&self, address: usize) -> W
+    {
+        return self.memory.get(address);
+    }
+
+    fn execute(&mut self, blocking: bool) -> Result<RunState<W>, IntcodeError>
+    {
+        let input = &self.input;
+        let output = &self.output;
+
+        let mut computer = Computer::default();
+        computer.set_memory_limit(self.memory_limit);
+        computer.resume_from(std::mem::take(&mut self.memory), self.instruction_pointer, self.relative_base_offset);
+
+        if blocking
+        {
+            computer.set_input_source(move || input.recv().ok());
+        }
+        else
+        {
+            computer.set_input_source(move || input.try_recv().ok());
+        }
+
+        // nobody listening anymore isn't an error for the program producing the output
+        computer.set_output(move |value| {
+            let _ = output.send(value);
+        });
+
+        let result = computer.run();
+
+        self.instruction_pointer = computer.instruction_pointer();
+        self.relative_base_offset = computer.relative_base_offset();
+        self.memory = computer.take_memory();
+
+        return result;
+    }
+}
@@ -0,0 +1,842 @@
+use crate::backend::IoBackend;
+use crate::disassembler::Instruction;
+use crate::error::{IntcodeError, IntcodeErrorKind};
+use crate::execute::{execute, Executed, Machine};
+use crate::history::{History, UndoRecord};
+use crate::isa::IsaProfile;
+use crate::limits::{run_limited, LimitedRun, RunLimits};
+use crate::io::{InputFn, OutputFn, IOQueue, stdin_input, stdout_output};
+use crate::opcode::{Opcode, ParameterMode};
+use crate::outputs::Outputs;
+use crate::profile::Profiler;
+use crate::session::{Session, SessionEvent};
+use crate::program::{Program, DEFAULT_MEMORY_LIMIT, MAX_ADDRESS};
+use crate::snapshot::Snapshot;
+use crate::trace::{Tracer, TraceRecord};
+use crate::word::Word;
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunState<W = i64>
+{
+    Running,
+    ProducedOutput(W),
+    AwaitingInput,
+    // a bounded output queue is full, the OUT instruction runs again once there's room
+    OutputBlocked,
+    Halted
+}
+
+// What ADD and MUL do with results that don't fit in the Word. Programs that
+// need more than 64 bits can run on a Computer with a wider one instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Arithmetic
+{
+    Wrapping,
+    // stops the program with an Overflow error
+    #[default]
+    Checked
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryWrite<W = i64>
+{
+    pub address: usize,
+    pub old_value: W,
+    pub new_value: W
+}
+
+// Runs programs on i64 values, or on any other Word: Computer::new makes an
+// i64 computer, Computer::<i128>::default() or Computer::<BigInt>::default()
+// one with wider values. Programs are loaded the same way either way.
+pub struct Computer<'a, W = i64>
+{
+    memory: Program<W>,
+    instruction_pointer: usize,
+    relative_base_offset: usize,
+    memory_limit: usize,
+    arithmetic: Arithmetic,
+    strict_isa: Option<IsaProfile>,
+    instruction_cache: Vec<Option<Opcode>>,
+    last_write: Option<MemoryWrite<W>>,
+    tracer: Option<Tracer<'a>>,
+    history: Option<History<W>>,
+    profiler: Option<Profiler>,
+    session: Option<Session<W>>,
+    // value of steps when the session recorder was set, session steps count from there
+    session_start: u64,
+    // instructions executed since the program was loaded
+    steps: u64,
+    input_fn: Option<InputFn<'a, W>>,
+    output_fn: Option<OutputFn<'a, W>>,
+    // kept so pending I/O can be saved in a snapshot
+    input_queue: Option<IOQueue<W>>,
+    output_queue: Option<IOQueue<W>>,
+    // the first error from an IoBackend, shared with its input and output
+    io_error: Rc<RefCell<Option<io::Error>>>
+}
+
+impl<'a, W: Word> Default for Computer<'a, W>
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            memory: Program::default(),
+            instruction_pointer: 0,
+            relative_base_offset: 0,
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+            arithmetic: Arithmetic::default(),
+            strict_isa: None,
+            instruction_cache: Vec::new(),
+            last_write: None,
+            tracer: None,
+            history: None,
+            profiler: None,
+            session: None,
+            session_start: 0,
+            steps: 0,
+            input_fn: None,
+            output_fn: None,
+            input_queue: None,
+            output_queue: None,
+            io_error: Rc::new(RefCell::new(None))
+        }
+    }
+}
+
+impl<'a> Computer<'a>
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+}
+
+impl<'a, W: Word> Computer<'a, W>
+{
+    pub fn load_program(&mut self, program: &Program)
+    {
+        self.memory = program.widen();
+        self.memory.set_memory_limit(self.memory_limit);
+        self.instruction_pointer = 0;
+        self.relative_base_offset = 0;
+        self.instruction_cache.clear();
+        self.last_write = None;
+        self.steps = 0;
+        self.clear_history();
+    }
+
+    // Ceiling on the memory a program can allocate, in values. Going over it
+    // stops the program with a MemoryLimitExceeded error.
+    pub fn set_memory_limit(&mut self, limit: usize)
+    {
+        self.memory_limit = limit;
+        self.memory.set_memory_limit(limit);
+    }
+
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic)
+    {
+        self.arithmetic = arithmetic;
+    }
+
+    // With a profile, opcodes and parameter modes it doesn't have and writes
+    // to immediate parameters are errors. Without one every opcode runs and
+    // an immediate write parameter is treated as a position.
+    pub fn set_strict_isa(&mut self, profile: Option<IsaProfile>)
+    {
+        self.strict_isa = profile;
+        // cached opcodes were checked against the old profile
+        self.instruction_cache.clear();
+    }
+
+    pub fn set_input<T: FnMut() -> W + 'a>(&mut self, mut input_fn: T)
+    {
+        self.input_fn = Some(Box::new(move || Some(input_fn())));
+        self.input_queue = None;
+    }
+
+    // input that can run dry, returning None makes the computer wait for input
+    pub(crate) fn set_input_source<T: FnMut() -> Option<W> + 'a>(&mut self, input_fn: T)
+    {
+        self.input_fn = Some(Box::new(input_fn));
+        self.input_queue = None;
+    }
+
+    pub fn set_output<T: FnMut(W) + 'a>(&mut self, output_fn: T)
+    {
+        self.output_fn = Some(Box::new(output_fn));
+        self.output_queue = None;
+    }
+
+    // Takes input from the iterator and hands outputs out one at a time
+    // instead of passing them to the output. The program has to be loaded already.
+    pub fn into_outputs<I>(mut self, inputs: I) -> Outputs<'a, W>
+        where I: IntoIterator<Item = W>,
+              I::IntoIter: 'a
+    {
+        let mut inputs = inputs.into_iter();
+        self.set_input_source(move || inputs.next());
+        self.set_output(|_| ());
+
+        return Outputs::new(self);
+    }
+
+    pub fn set_input_queue(&mut self, input: &IOQueue<W>)
+    {
+        self.input_queue = Some(input.clone());
+        let input = input.clone();
+        self.input_fn = Some(Box::new(move || {
+            input.pop_front()
+        }));
+    }
+
+    pub fn set_output_queue(&mut self, output: &IOQueue<W>)
+    {
+        self.output_queue = Some(output.clone());
+        let output = output.clone();
+        // write_output checks there's room before the value gets here
+        self.output_fn = Some(Box::new(move |o| { let _ = output.push_back(o); }));
+    }
+
+    pub fn set_input_stdin(&mut self)
+    {
+        self.input_fn = Some(Box::new(stdin_input));
+        self.input_queue = None;
+    }
+
+    pub fn set_output_stdout(&mut self)
+    {
+        self.output_fn = Some(Box::new(stdout_output));
+        self.output_queue = None;
+    }
+
+    // Input and output both go through the backend. A failed read or write
+    // stops the computer with an Io error, take_io_error says what went wrong.
+    // Until it's called every IN and OUT fails the same way.
+    pub fn set_io_backend<B: IoBackend<W> + 'a>(&mut self, backend: B)
+    {
+        let backend = Rc::new(RefCell::new(backend));
+        self.io_error = Rc::new(RefCell::new(None));
+
+        let (input, error) = (Rc::clone(&backend), Rc::clone(&self.io_error));
+        self.input_fn = Some(Box::new(move || {
+            match input.borrow_mut().read_value()
+            {
+                Ok(value) => value,
+                Err(e) => {
+                    error.borrow_mut().get_or_insert(e);
+                    None
+                }
+            }
+        }));
+
+        let error = Rc::clone(&self.io_error);
+        self.output_fn = Some(Box::new(move |o| {
+            if let Err(e) = backend.borrow_mut().write_value(o)
+            {
+                error.borrow_mut().get_or_insert(e);
+            }
+        }));
+
+        self.input_queue = None;
+        self.output_queue = None;
+    }
+
+    pub fn take_io_error(&mut self) -> Option<io::Error>
+    {
+        return self.io_error.borrow_mut().take();
+    }
+
+    fn check_io(&self) -> Result<(), IntcodeError>
+    {
+        if self.io_error.borrow().is_some()
+        {
+            return Err(self.error(IntcodeErrorKind::Io));
+        }
+
+        return Ok(());
+    }
+
+    pub fn set_tracer(&mut self, tracer: Tracer<'a>)
+    {
+        self.tracer = Some(tracer);
+    }
+
+    // stops tracing, call Tracer::finish on the result to flush it
+    pub fn take_tracer(&mut self) -> Option<Tracer<'a>>
+    {
+        return self.tracer.take();
+    }
+
+    // Pending I/O is only saved for IOQueues, other inputs and outputs are
+    // left out of the snapshot.
+    pub fn snapshot(&self) -> Snapshot<W>
+    {
+        Snapshot
+        {
+            memory: self.memory.clone(),
+            instruction_pointer: self.instruction_pointer,
+            relative_base_offset: self.relative_base_offset,
+            pending_input: self.input_queue.as_ref().map(|q| q.values()),
+            pending_output: self.output_queue.as_ref().map(|q| q.values())
+        }
+    }
+
+    // Restores memory and registers. Pending I/O is written back into the
+    // IOQueues currently connected, the I/O setup itself is left alone.
+    pub fn restore(&mut self, snapshot: &Snapshot<W>)
+    {
+        self.resume_from(snapshot.memory.clone(), snapshot.instruction_pointer, snapshot.relative_base_offset);
+
+        if let (Some(queue), Some(values)) = (&self.input_queue, &snapshot.pending_input)
+        {
+            queue.replace(values);
+        }
+
+        if let (Some(queue), Some(values)) = (&self.output_queue, &snapshot.pending_output)
+        {
+            queue.replace(values);
+        }
+    }
+
+    pub(crate) fn resume_from(&mut self, memory: Program<W>, instruction_pointer: usize, relative_base_offset: usize)
+    {
+        self.memory = memory;
+        self.memory.set_memory_limit(self.memory_limit);
+        self.instruction_pointer = instruction_pointer;
+        self.relative_base_offset = relative_base_offset;
+        self.instruction_cache.clear();
+        self.last_write = None;
+        self.clear_history();
+    }
+
+    // moves the memory out, leaving the computer with an empty program
+    pub(crate) fn take_memory(&mut self) -> Program<W>
+    {
+        self.instruction_cache.clear();
+        return std::mem::take(&mut self.memory);
+    }
+
+    // Keeps an undo log of the last `limit` steps for step_back and run_back_to.
+    // Changes made with set_value between steps aren't logged.
+    pub fn record_history(&mut self, limit: usize)
+    {
+        self.history = Some(History::new(limit));
+    }
+
+    pub fn stop_recording_history(&mut self)
+    {
+        self.history = None;
+    }
+
+    // number of steps that can currently be undone
+    pub fn history_len(&self) -> usize
+    {
+        return self.history.as_ref().map_or(0, |h| h.len());
+    }
+
+    fn clear_history(&mut self)
+    {
+        if let Some(history) = &mut self.history
+        {
+            history.clear();
+        }
+    }
+
+    // Undoes the last step, returns false when there is nothing left to undo.
+    // Input read from an IOQueue is put back and output sent to an IOQueue is
+    // taken back, other inputs and outputs can't be undone.
+    pub fn step_back(&mut self) -> bool
+    {
+        let record = match self.history.as_mut().and_then(|h| h.pop())
+        {
+            Some(record) => record,
+            None => return false
+        };
+
+        if let Some(write) = record.write
+        {
+            self.memory[write.address] = write.old_value;
+
+            if let Some(entry) = self.instruction_cache.get_mut(write.address)
+            {
+                *entry = None;
+            }
+        }
+
+        self.memory.shrink_to(record.memory_len);
+        self.instruction_pointer = record.instruction_pointer;
+        self.relative_base_offset = record.relative_base_offset;
+        self.last_write = None;
+        self.steps = self.steps.saturating_sub(1);
+
+        if let (Some(step), Some(session)) = (self.session_step(), &mut self.session)
+        {
+            session.unrecord(step);
+        }
+
+        if let (Some(value), Some(queue)) = (record.consumed_input, &self.input_queue)
+        {
+            queue.unread(value);
+        }
+
+        if let (true, Some(queue)) = (record.produced_output, &self.output_queue)
+        {
+            queue.pop_back();
+        }
+
+        return true;
+    }
+
+    // Steps back until the instruction at address is about to be executed again.
+    // Returns false if the history ran out first, leaving the oldest state we have.
+    pub fn run_back_to(&mut self, address: usize) -> bool
+    {
+        while self.step_back()
+        {
+            if self.instruction_pointer == address
+            {
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    pub fn set_profiler(&mut self, profiler: Profiler)
+    {
+        self.profiler = Some(profiler);
+    }
+
+    // stops profiling and hands back what was counted
+    pub fn take_profiler(&mut self) -> Option<Profiler>
+    {
+        return self.profiler.take();
+    }
+
+    // Records every value read and written from now on, whatever the input
+    // and output are connected to. Steps in the session count from here, to
+    // replay a session that didn't start with the program take a snapshot now
+    // and replay from it.
+    pub fn set_session_recorder(&mut self, session: Session<W>)
+    {
+        self.session = Some(session);
+        self.session_start = self.steps;
+    }
+
+    // None when stepped back to before the recording started
+    fn session_step(&self) -> Option<u64>
+    {
+        return self.steps.checked_sub(self.session_start);
+    }
+
+    // stops recording and hands back the session, ready to save or replay
+    pub fn take_session_recorder(&mut self) -> Option<Session<W>>
+    {
+        return self.session.take();
+    }
+
+    // instructions executed since the program was loaded, stepping back takes them off again
+    pub fn instructions_executed(&self) -> u64
+    {
+        return self.steps;
+    }
+
+    pub fn is_halted(&self) -> bool
+    {
+        if self.memory.is_empty()
+        {
+            return true;
+        }
+        else
+        {
+            return matches!(self.current_opcode(), Some(Opcode::Halt));
+        }
+    }
+
+    pub fn run(&mut self) -> Result<RunState<W>, IntcodeError>
+    {
+        loop
+        {
+            match self.step()?
+            {
+                RunState::Running | RunState::ProducedOutput(_) => (),
+                state => return Ok(state)
+            }
+        }
+    }
+
+    // Like run, but gives up once the program has executed max_instructions
+    // or the deadline has passed, whichever comes first.
+    pub fn run_with_limits(&mut self, limits: &RunLimits) -> Result<LimitedRun<W>, IntcodeError>
+    {
+        return run_limited(self, limits, Self::step, Self::instruction_pointer);
+    }
+
+    pub fn run_until_output(&mut self) -> Result<RunState<W>, IntcodeError>
+    {
+        loop
+        {
+            match self.step()?
+            {
+                RunState::Running => (),
+                state => return Ok(state)
+            }
+        }
+    }
+
+    pub fn step(&mut self) -> Result<RunState<W>, IntcodeError>
+    {
+        self.last_write = None;
+
+        let state = if self.tracer.is_some() || self.history.is_some() || self.profiler.is_some()
+        {
+            self.observed_step()?
+        }
+        else
+        {
+            self.execute()?
+        };
+
+        if matches!(state, RunState::Running | RunState::ProducedOutput(_))
+        {
+            self.steps += 1;
+        }
+
+        return Ok(state);
+    }
+
+    // step with tracing, history or profiling, kept apart so plain runs stay fast
+    #[inline(never)]
+    fn observed_step(&mut self) -> Result<RunState<W>, IntcodeError>
+    {
+        let trace_record = match &self.tracer
+        {
+            Some(tracer) if tracer.wants(self.instruction_pointer) => self.trace_record(),
+            _ => None
+        };
+
+        let instruction_pointer = self.instruction_pointer;
+        let relative_base_offset = self.relative_base_offset;
+        let memory_len = self.memory.len();
+        let reads_input = matches!(self.current_opcode(), Some(Opcode::Input(_)));
+        let profiled = match &self.profiler
+        {
+            Some(_) => Instruction::decode(&self.memory, instruction_pointer).map(|i| {
+                let reads = self.read_addresses(&i);
+                (i, reads)
+            }),
+            None => None
+        };
+
+        let state = self.execute()?;
+
+        // nothing was executed
+        if matches!(state, RunState::AwaitingInput | RunState::OutputBlocked)
+        {
+            return Ok(state);
+        }
+
+        if let Some(tracer) = &mut self.tracer
+        {
+            match trace_record
+            {
+                Some(record) => tracer.record(record, self.last_write.clone()),
+                None => tracer.skip()
+            }
+        }
+
+        if let (Some(profiler), Some((instruction, reads))) = (&mut self.profiler, profiled)
+        {
+            profiler.record(&instruction, &reads, self.last_write.as_ref().map(|w| w.address), self.instruction_pointer);
+        }
+
+        if let (Some(history), true) = (&mut self.history, state != RunState::Halted)
+        {
+            history.push(UndoRecord
+            {
+                instruction_pointer,
+                relative_base_offset,
+                memory_len,
+                write: self.last_write.clone(),
+                consumed_input: if reads_input {self.last_write.as_ref().map(|w| w.new_value.clone())} else {None},
+                produced_output: matches!(state, RunState::ProducedOutput(_))
+            });
+        }
+
+        return Ok(state);
+    }
+
+    fn execute(&mut self) -> Result<RunState<W>, IntcodeError>
+    {
+        if self.memory.is_empty()
+        {
+            return Ok(RunState::Halted);
+        }
+
+        let opcode = self.decode()?;
+
+        match execute(self, opcode)?
+        {
+            Executed::Running => {
+                if self.session.is_some() && matches!(opcode, Opcode::Input(_))
+                {
+                    if let (Some(write), Some(step), Some(session)) = (self.last_write.as_ref(), self.session_step(), &mut self.session)
+                    {
+                        session.record(SessionEvent::Input {step, value: write.new_value.clone()});
+                    }
+                }
+
+                return Ok(RunState::Running);
+            },
+            Executed::Output(value) => {
+                if let (Some(step), Some(session)) = (self.session_step(), &mut self.session)
+                {
+                    session.record(SessionEvent::Output {step, value: value.clone()});
+                }
+
+                return Ok(RunState::ProducedOutput(value));
+            },
+            Executed::OutputBlocked => return Ok(RunState::OutputBlocked),
+            Executed::AwaitingInput => return Ok(RunState::AwaitingInput),
+            Executed::Halted => return Ok(RunState::Halted)
+        }
+    }
+
+    fn trace_record(&self) -> Option<TraceRecord<W>>
+    {
+        let instruction = Instruction::decode(&self.memory, self.instruction_pointer)?;
+        let writes = instruction.opcode.writes_last_parameter();
+        let modes = instruction.opcode.parameter_modes();
+        let relative_base = self.relative_base_offset as i64;
+
+        let operands = instruction.parameters.iter().zip(modes.iter()).enumerate().map(|(i, (p, mode))| {
+            let address = match (mode, p.to_i64())
+            {
+                (ParameterMode::Immediate, _) => return p.clone(),
+                // too wide to be an address, the instruction fails and the trace shows the parameter
+                (_, None) => return p.clone(),
+                (ParameterMode::Position, Some(p)) => p,
+                // the instruction fails with AddressOutOfRange, the trace just shows the largest address
+                (ParameterMode::Relative, Some(p)) => p.saturating_add(relative_base)
+            };
+
+            if writes && i == modes.len() - 1
+            {
+                W::from_i64(address)
+            }
+            else
+            {
+                self.get_value(address.max(0) as usize)
+            }
+        }).collect();
+
+        return Some(TraceRecord
+        {
+            instruction_pointer: self.instruction_pointer,
+            opcode: instruction.opcode,
+            parameters: instruction.parameters,
+            operands,
+            relative_base_offset: self.relative_base_offset
+        });
+    }
+
+    // memory an instruction is about to read, not counting immediates and the destination
+    fn read_addresses(&self, instruction: &Instruction<W>) -> Vec<usize>
+    {
+        let modes = instruction.opcode.parameter_modes();
+        let sources = modes.len() - (instruction.opcode.writes_last_parameter() as usize);
+
+        return modes.iter().zip(instruction.parameters.iter()).take(sources).filter_map(|(mode, p)| {
+            let address = match mode
+            {
+                ParameterMode::Position => p.to_i64()?,
+                ParameterMode::Immediate => return None,
+                ParameterMode::Relative => p.to_i64()?.checked_add(self.relative_base_offset as i64)?
+            };
+
+            if address >= 0 {Some(address as usize)} else {None}
+        }).collect();
+    }
+
+    fn fetch(&self) -> W
+    {
+        return self.memory.get(self.instruction_pointer);
+    }
+
+    fn decode(&mut self) -> Result<Opcode, IntcodeError>
+    {
+        let ip = self.instruction_pointer;
+
+        if let Some(Some(opcode)) = self.instruction_cache.get(ip)
+        {
+            return Ok(*opcode);
+        }
+
+        let opcode = match self.fetch().to_i64().and_then(Opcode::from_intcode)
+        {
+            Some(opcode) => opcode,
+            None => return Err(self.error(IntcodeErrorKind::InvalidOpcode))
+        };
+
+        if let Some(profile) = self.strict_isa
+        {
+            profile.check(&opcode).map_err(|kind| self.error(kind))?;
+        }
+
+        // only the dense part of memory is cached, sparse pages can be anywhere
+        if ip < self.memory.memory.len()
+        {
+            if self.instruction_cache.len() < self.memory.memory.len()
+            {
+                self.instruction_cache.resize(self.memory.memory.len(), None);
+            }
+
+            self.instruction_cache[ip] = Some(opcode);
+        }
+
+        return Ok(opcode);
+    }
+
+    fn write_memory(&mut self, address: usize, value: W) -> Result<(), IntcodeError>
+    {
+        let old_value = match self.memory.get_mut(address)
+        {
+            Some(slot) => std::mem::replace(slot, value.clone()),
+            None if address > MAX_ADDRESS => return Err(self.error(IntcodeErrorKind::AddressOutOfRange)),
+            None => return Err(self.error(IntcodeErrorKind::MemoryLimitExceeded(address)))
+        };
+
+        self.last_write = Some(MemoryWrite {address, old_value, new_value: value});
+
+        // self-modifying code: the next time this address is executed it has to be decoded again
+        if let Some(entry) = self.instruction_cache.get_mut(address)
+        {
+            *entry = None;
+        }
+
+        return Ok(());
+    }
+
+    pub fn get_value(&self, address: usize) -> W
+    {
+        return self.memory.get(address);
+    }
+
+    // panics if the write would go over the memory limit, see try_set_value
+    pub fn set_value(&mut self, address: usize, val: W)
+    {
+        if let Err(e) = self.try_set_value(address, val)
+        {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_set_value(&mut self, address: usize, val: W) -> Result<(), IntcodeError>
+    {
+        return self.write_memory(address, val);
+    }
+
+    pub fn instruction_pointer(&self) -> usize
+    {
+        return self.instruction_pointer;
+    }
+
+    pub fn relative_base_offset(&self) -> usize
+    {
+        return self.relative_base_offset;
+    }
+
+    // the memory write made by the last step, if it made one
+    pub fn last_write(&self) -> Option<MemoryWrite<W>>
+    {
+        return self.last_write.clone();
+    }
+
+    pub(crate) fn memory(&self) -> &Program<W>
+    {
+        return &self.memory;
+    }
+
+    pub(crate) fn current_opcode(&self) -> Option<Opcode>
+    {
+        return self.fetch().to_i64().and_then(Opcode::from_intcode);
+    }
+}
+
+impl<'a, W: Word> Machine for Computer<'a, W>
+{
+    type Word = W;
+
+    #[inline]
+    fn memory(&self) -> &Program<W>
+    {
+        return &self.memory;
+    }
+
+    #[inline]
+    fn instruction_pointer(&self) -> usize
+    {
+        return self.instruction_pointer;
+    }
+
+    #[inline]
+    fn set_instruction_pointer(&mut self, address: usize)
+    {
+        self.instruction_pointer = address;
+    }
+
+    #[inline]
+    fn relative_base(&self) -> usize
+    {
+        return self.relative_base_offset;
+    }
+
+    #[inline]
+    fn set_relative_base(&mut self, relative_base: usize)
+    {
+        self.relative_base_offset = relative_base;
+    }
+
+    #[inline]
+    fn arithmetic(&self) -> Arithmetic
+    {
+        return self.arithmetic;
+    }
+
+    #[inline(always)]
+    fn write(&mut self, address: usize, value: W) -> Result<(), IntcodeError>
+    {
+        return self.write_memory(address, value);
+    }
+
+    #[inline]
+    fn input(&mut self) -> Result<Option<W>, IntcodeError>
+    {
+        let value = self.input_fn.as_mut().and_then(|input_f| input_f());
+        self.check_io()?;
+
+        return Ok(value);
+    }
+
+    #[inline]
+    fn can_output(&self) -> bool
+    {
+        return !self.output_queue.as_ref().is_some_and(|q| q.is_full());
+    }
+
+    #[inline]
+    fn output(&mut self, value: W) -> Result<(), IntcodeError>
+    {
+        if let Some(output_f) = &mut self.output_fn
+        {
+            output_f(value);
+        }
+
+        return self.check_io();
+    }
+}
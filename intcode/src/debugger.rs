@@ -0,0 +1,470 @@
+use crate::computer::{Computer, MemoryWrite, RunState};
+use crate::disassembler::{disassemble_instruction, Instruction};
+use crate::error::IntcodeError;
+use crate::io::IOQueue;
+use crate::opcode::Opcode;
+use crate::program::Program;
+use crate::word::Word;
+
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+
+// number of values shown on a single line by the examine command
+const VALUES_PER_LINE: usize = 8;
+
+// most values or instructions shown at once by the examine and list commands
+const MAX_SHOWN: usize = 4096;
+
+const HELP: &str = "\
+step [n]              execute n instructions (default 1)
+continue              run until a breakpoint, watchpoint, input request or halt
+output                like continue, but also stop after the next output
+history [n]           keep the last n steps for back and rewind, 0 turns it off
+back [n]              undo n instructions (default 1)
+rewind <addr>         undo instructions until addr is about to run again
+break <addr|MNEMONIC> stop before executing an address or opcode
+delete <addr|MNEMONIC> remove a breakpoint
+watch <addr>          stop after a write to an address
+unwatch <addr>        remove a watchpoint
+info                  list breakpoints and watchpoints
+regs                  show the instruction pointer and relative base
+list [addr] [n]       disassemble n instructions starting at addr (default ip), at most 4096
+x <addr> [n]          show n memory values starting at addr, at most 4096
+set <addr> <value>    write a value to memory
+input <value>...      queue values for the program to read
+help                  show this message
+quit                  leave the debugger";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason<W = i64>
+{
+    Stepped,
+    Breakpoint(usize),
+    OpcodeBreakpoint(usize),
+    Watchpoint(MemoryWrite<W>),
+    Output(W),
+    AwaitingInput,
+    OutputBlocked,
+    Halted
+}
+
+pub struct Debugger<'a, W = i64>
+{
+    computer: Computer<'a, W>,
+    input: Option<IOQueue<W>>,
+    outputs: Vec<W>,
+    breakpoints: BTreeSet<usize>,
+    opcode_breakpoints: BTreeSet<&'static str>,
+    watchpoints: BTreeSet<usize>,
+    // steps kept for back and rewind, 0 when history is off
+    history_limit: usize
+}
+
+impl<'a> Debugger<'a>
+{
+    pub fn from_program(program: &Program) -> Self
+    {
+        return Self::with_program(Computer::new(), program);
+    }
+}
+
+impl<'a, W: Word> Debugger<'a, W>
+{
+    // Debugger for a computer with its I/O already set up. The input command
+    // only works for debuggers made with from_program or with_program.
+    // Stepping back is off until record_history is called, keeping history
+    // slows every step down.
+    pub fn new(computer: Computer<'a, W>) -> Self
+    {
+        Self
+        {
+            computer,
+            input: None,
+            outputs: Vec::new(),
+            breakpoints: BTreeSet::new(),
+            opcode_breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            history_limit: 0
+        }
+    }
+
+    // loads the program into the computer, which can have any Word, and takes its input from the debugger
+    pub fn with_program(mut computer: Computer<'a, W>, program: &Program) -> Self
+    {
+        let input = IOQueue::default();
+        computer.load_program(program);
+        computer.set_input_queue(&input);
+
+        let mut debugger = Self::new(computer);
+        debugger.input = Some(input);
+
+        return debugger;
+    }
+
+    // keeps the last limit steps so they can be undone, 0 stops recording
+    pub fn record_history(&mut self, limit: usize)
+    {
+        if limit == 0
+        {
+            self.computer.stop_recording_history();
+        }
+        else
+        {
+            self.computer.record_history(limit);
+        }
+
+        self.history_limit = limit;
+    }
+
+    pub fn computer(&self) -> &Computer<'a, W>
+    {
+        return &self.computer;
+    }
+
+    pub fn computer_mut(&mut self) -> &mut Computer<'a, W>
+    {
+        return &mut self.computer;
+    }
+
+    // returns false when the computer wasn't made by from_program
+    pub fn feed_input(&self, value: W) -> bool
+    {
+        match &self.input
+        {
+            Some(input) => {
+                input.push_back(value).unwrap();
+                true
+            },
+            None => false
+        }
+    }
+
+    // outputs produced since the last call
+    pub fn take_outputs(&mut self) -> Vec<W>
+    {
+        return std::mem::take(&mut self.outputs);
+    }
+
+    pub fn add_breakpoint(&mut self, address: usize)
+    {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) -> bool
+    {
+        return self.breakpoints.remove(&address);
+    }
+
+    // returns false if the mnemonic isn't a known opcode
+    pub fn add_opcode_breakpoint(&mut self, mnemonic: &str) -> bool
+    {
+        match Opcode::from_mnemonic(mnemonic)
+        {
+            Some(opcode) => {
+                self.opcode_breakpoints.insert(opcode.mnemonic());
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn remove_opcode_breakpoint(&mut self, mnemonic: &str) -> bool
+    {
+        match Opcode::from_mnemonic(mnemonic)
+        {
+            Some(opcode) => self.opcode_breakpoints.remove(opcode.mnemonic()),
+            None => false
+        }
+    }
+
+    pub fn add_watchpoint(&mut self, address: usize)
+    {
+        self.watchpoints.insert(address);
+    }
+
+    pub fn remove_watchpoint(&mut self, address: usize) -> bool
+    {
+        return self.watchpoints.remove(&address);
+    }
+
+    pub fn step(&mut self) -> Result<StopReason<W>, IntcodeError>
+    {
+        let reason = match self.computer.step()?
+        {
+            RunState::Halted => StopReason::Halted,
+            RunState::AwaitingInput => StopReason::AwaitingInput,
+            RunState::OutputBlocked => StopReason::OutputBlocked,
+            RunState::ProducedOutput(output) => {
+                self.outputs.push(output.clone());
+                StopReason::Output(output)
+            },
+            RunState::Running => StopReason::Stepped
+        };
+
+        if let Some(write) = self.computer.last_write()
+        {
+            if self.watchpoints.contains(&write.address)
+            {
+                return Ok(StopReason::Watchpoint(write));
+            }
+        }
+
+        return Ok(reason);
+    }
+
+    pub fn continue_execution(&mut self) -> Result<StopReason<W>, IntcodeError>
+    {
+        return self.resume(false);
+    }
+
+    pub fn continue_until_output(&mut self) -> Result<StopReason<W>, IntcodeError>
+    {
+        return self.resume(true);
+    }
+
+    fn resume(&mut self, stop_on_output: bool) -> Result<StopReason<W>, IntcodeError>
+    {
+        loop
+        {
+            match self.step()?
+            {
+                StopReason::Stepped => (),
+                StopReason::Output(_) if !stop_on_output => (),
+                reason => return Ok(reason)
+            }
+
+            let ip = self.computer.instruction_pointer();
+
+            if self.breakpoints.contains(&ip)
+            {
+                return Ok(StopReason::Breakpoint(ip));
+            }
+
+            if let Some(opcode) = self.computer.current_opcode()
+            {
+                if self.opcode_breakpoints.contains(opcode.mnemonic())
+                {
+                    return Ok(StopReason::OpcodeBreakpoint(ip));
+                }
+            }
+        }
+    }
+
+    fn describe(&self, address: usize) -> String
+    {
+        match disassemble_instruction(self.computer.memory(), address)
+        {
+            Some(instruction) => format!("{:04}: {}", address, instruction),
+            None => format!("{:04}: .data {}", address, self.computer.get_value(address))
+        }
+    }
+
+    fn report<T: Write>(&mut self, result: Result<StopReason<W>, IntcodeError>, output: &mut T) -> io::Result<()>
+    {
+        for value in self.take_outputs()
+        {
+            writeln!(output, "output: {}", value)?;
+        }
+
+        match result
+        {
+            Ok(StopReason::Stepped) | Ok(StopReason::Output(_)) => (),
+            Ok(StopReason::Breakpoint(address)) => writeln!(output, "breakpoint at {:04}", address)?,
+            Ok(StopReason::OpcodeBreakpoint(address)) => writeln!(output, "opcode breakpoint at {:04}", address)?,
+            Ok(StopReason::Watchpoint(w)) => writeln!(output, "watchpoint: [{}] {} -> {}", w.address, w.old_value, w.new_value)?,
+            Ok(StopReason::AwaitingInput) => writeln!(output, "waiting for input")?,
+            Ok(StopReason::OutputBlocked) => writeln!(output, "output queue full")?,
+            Ok(StopReason::Halted) => writeln!(output, "halted")?,
+            Err(e) => writeln!(output, "error: {}", e)?
+        }
+
+        return writeln!(output, "{}", self.describe(self.computer.instruction_pointer()));
+    }
+
+    // runs a single debugger command, returns false when the session should end
+    pub fn command<T: Write>(&mut self, line: &str, output: &mut T) -> io::Result<bool>
+    {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let number = |i: usize| words.get(i).and_then(|w| w.parse::<usize>().ok());
+
+        match words.first().copied()
+        {
+            None => (),
+            Some("s") | Some("step") => {
+                let mut result = Ok(StopReason::Stepped);
+                for _ in 0..number(1).unwrap_or(1)
+                {
+                    result = self.step();
+                    if result != Ok(StopReason::Stepped) && !matches!(result, Ok(StopReason::Output(_)))
+                    {
+                        break;
+                    }
+                }
+                self.report(result, output)?;
+            },
+            Some("c") | Some("continue") => {
+                let result = self.continue_execution();
+                self.report(result, output)?;
+            },
+            Some("o") | Some("output") => {
+                let result = self.continue_until_output();
+                self.report(result, output)?;
+            },
+            Some("history") => match (words.get(1), number(1))
+            {
+                (None, _) if self.history_limit == 0 => writeln!(output, "history is off")?,
+                (None, _) => writeln!(output, "keeping {} steps, {} recorded", self.history_limit, self.computer.history_len())?,
+                (Some(_), Some(limit)) => self.record_history(limit),
+                (Some(_), None) => writeln!(output, "usage: history [n]")?
+            },
+            Some("back") | Some("rewind") if self.history_limit == 0 => {
+                writeln!(output, "history is off, turn it on with history <n>")?;
+            },
+            Some("back") => {
+                let steps = number(1).unwrap_or(1);
+                let undone = (0..steps).take_while(|_| self.computer.step_back()).count();
+                if undone < steps
+                {
+                    writeln!(output, "history exhausted after {} steps", undone)?;
+                }
+                writeln!(output, "{}", self.describe(self.computer.instruction_pointer()))?;
+            },
+            Some("rewind") => match number(1)
+            {
+                Some(address) => {
+                    if !self.computer.run_back_to(address)
+                    {
+                        writeln!(output, "history exhausted before reaching {:04}", address)?;
+                    }
+                    writeln!(output, "{}", self.describe(self.computer.instruction_pointer()))?;
+                },
+                None => writeln!(output, "usage: rewind <addr>")?
+            },
+            Some("b") | Some("break") => match (number(1), words.get(1))
+            {
+                (Some(address), _) => self.add_breakpoint(address),
+                (None, Some(m)) if self.add_opcode_breakpoint(m) => (),
+                _ => writeln!(output, "usage: break <addr|MNEMONIC>")?
+            },
+            Some("d") | Some("delete") => match (number(1), words.get(1))
+            {
+                (Some(address), _) if self.remove_breakpoint(address) => (),
+                (None, Some(m)) if self.remove_opcode_breakpoint(m) => (),
+                _ => writeln!(output, "no such breakpoint")?
+            },
+            Some("w") | Some("watch") => match number(1)
+            {
+                Some(address) => self.add_watchpoint(address),
+                None => writeln!(output, "usage: watch <addr>")?
+            },
+            Some("unwatch") => match number(1)
+            {
+                Some(address) if self.remove_watchpoint(address) => (),
+                _ => writeln!(output, "no such watchpoint")?
+            },
+            Some("info") => {
+                let breakpoints: Vec<String> = self.breakpoints.iter().map(|a| a.to_string())
+                    .chain(self.opcode_breakpoints.iter().map(|m| m.to_string()))
+                    .collect();
+                let watchpoints: Vec<String> = self.watchpoints.iter().map(|a| a.to_string()).collect();
+                writeln!(output, "breakpoints: {}", breakpoints.join(", "))?;
+                writeln!(output, "watchpoints: {}", watchpoints.join(", "))?;
+            },
+            Some("r") | Some("regs") => {
+                writeln!(output, "ip: {}  rb: {}", self.computer.instruction_pointer(), self.computer.relative_base_offset())?;
+                writeln!(output, "{}", self.describe(self.computer.instruction_pointer()))?;
+            },
+            Some("l") | Some("list") => {
+                let mut address = Some(number(1).unwrap_or_else(|| self.computer.instruction_pointer()));
+                for _ in 0..number(2).unwrap_or(10).min(MAX_SHOWN)
+                {
+                    // the listing stops at the end of the address space
+                    let current = match address
+                    {
+                        Some(current) => current,
+                        None => break
+                    };
+
+                    writeln!(output, "{}", self.describe(current))?;
+                    address = match Instruction::decode(self.computer.memory(), current)
+                    {
+                        Some(instruction) => Some(instruction.next_address()),
+                        None => current.checked_add(1)
+                    };
+                }
+            },
+            Some("x") => match number(1)
+            {
+                Some(start) => match start.checked_add(number(2).unwrap_or(1).min(MAX_SHOWN))
+                {
+                    Some(end) => {
+                        let values: Vec<String> = (start..end).map(|a| self.computer.get_value(a).to_string()).collect();
+                        for (i, chunk) in values.chunks(VALUES_PER_LINE).enumerate()
+                        {
+                            writeln!(output, "{:04}: {}", start + i * VALUES_PER_LINE, chunk.join(" "))?;
+                        }
+                    },
+                    None => writeln!(output, "address out of range")?
+                },
+                None => writeln!(output, "usage: x <addr> [n]")?
+            },
+            Some("set") => match (number(1), words.get(2).and_then(|w| w.parse::<W>().ok()))
+            {
+                (Some(address), Some(value)) => if let Err(e) = self.computer.try_set_value(address, value)
+                {
+                    writeln!(output, "error: {}", e)?;
+                },
+                _ => writeln!(output, "usage: set <addr> <value>")?
+            },
+            Some("i") | Some("input") => {
+                for word in &words[1..]
+                {
+                    match word.parse::<W>()
+                    {
+                        Ok(value) => if !self.feed_input(value)
+                        {
+                            writeln!(output, "this computer doesn't take input from the debugger")?;
+                        },
+                        Err(_) => writeln!(output, "not a number: {}", word)?
+                    }
+                }
+            },
+            Some("h") | Some("help") => writeln!(output, "{}", HELP)?,
+            Some("q") | Some("quit") => return Ok(false),
+            Some(other) => writeln!(output, "unknown command {}, try help", other)?
+        }
+
+        return Ok(true);
+    }
+
+    pub fn repl<R: BufRead, T: Write>(&mut self, input: R, mut output: T) -> io::Result<()>
+    {
+        let mut last_command = String::new();
+        let mut lines = input.lines();
+
+        writeln!(output, "{}", self.describe(self.computer.instruction_pointer()))?;
+
+        loop
+        {
+            write!(output, "(icdb) ")?;
+            output.flush()?;
+
+            let line = match lines.next()
+            {
+                Some(line) => line?,
+                None => return Ok(())
+            };
+
+            // an empty line repeats the last command, like gdb
+            if !line.trim().is_empty()
+            {
+                last_command = line;
+            }
+
+            if !self.command(&last_command, &mut output)?
+            {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,935 @@
+use crate::disassembler::{find_code, Instruction};
+use crate::opcode::{Opcode, ParameterMode};
+use crate::program::Program;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+const INDENT: &str = "    ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Parameter
+{
+    mode: ParameterMode,
+    value: i64
+}
+
+impl Parameter
+{
+    fn constant(&self) -> Option<i64>
+    {
+        if self.mode == ParameterMode::Immediate {Some(self.value)} else {None}
+    }
+
+    fn is_relative(&self, offset: i64) -> bool
+    {
+        return self.mode == ParameterMode::Relative && self.value == offset;
+    }
+}
+
+fn parameters(instruction: &Instruction) -> Vec<Parameter>
+{
+    instruction.opcode.parameter_modes().into_iter()
+        .zip(instruction.parameters.iter())
+        .map(|(mode, &value)| Parameter {mode, value})
+        .collect()
+}
+
+// where the instruction writes its result, if it writes one
+fn destination(instruction: &Instruction) -> Option<Parameter>
+{
+    if instruction.opcode.writes_last_parameter() {parameters(instruction).pop()} else {None}
+}
+
+// the parameters that are read, the destination of a write isn't one of them
+fn sources(instruction: &Instruction) -> Vec<Parameter>
+{
+    let mut parameters = parameters(instruction);
+    if instruction.opcode.writes_last_parameter()
+    {
+        parameters.pop();
+    }
+
+    return parameters;
+}
+
+// value an ADD or MUL of two immediates writes
+fn constant_result(instruction: &Instruction) -> Option<i64>
+{
+    let parameters = parameters(instruction);
+
+    match instruction.opcode
+    {
+        Opcode::Add(..) => parameters[0].constant()?.checked_add(parameters[1].constant()?),
+        Opcode::Mul(..) => parameters[0].constant()?.checked_mul(parameters[1].constant()?),
+        _ => None
+    }
+}
+
+// the parameter an ADD x, 0 or MUL x, 1 copies
+fn copied(instruction: &Instruction) -> Option<Parameter>
+{
+    let parameters = parameters(instruction);
+    let identity = match instruction.opcode
+    {
+        Opcode::Add(..) => 0,
+        Opcode::Mul(..) => 1,
+        _ => return None
+    };
+
+    match (parameters[0].constant(), parameters[1].constant())
+    {
+        (_, Some(c)) if c == identity => Some(parameters[0]),
+        (Some(c), _) if c == identity => Some(parameters[1]),
+        _ => None
+    }
+}
+
+// an unconditional jump to the return address on top of the stack
+fn is_return(instruction: &Instruction) -> bool
+{
+    return instruction.is_unconditional_jump() && parameters(instruction)[1].is_relative(0);
+}
+
+// a conditional jump whose immediate condition means it's never taken
+fn never_jumps(instruction: &Instruction) -> bool
+{
+    return instruction.is_jump() && parameters(instruction)[0].constant().is_some() && !instruction.is_unconditional_jump();
+}
+
+#[derive(Debug, Clone)]
+struct Condition
+{
+    left: String,
+    operator: &'static str,
+    right: String
+}
+
+impl Condition
+{
+    fn negate(&self) -> Self
+    {
+        let operator = match self.operator
+        {
+            "<" => ">=",
+            ">=" => "<",
+            "==" => "!=",
+            _ => "=="
+        };
+
+        return Self {left: self.left.clone(), operator, right: self.right.clone()};
+    }
+
+    fn render(&self) -> String
+    {
+        return format!("{} {} {}", self.left, self.operator, self.right);
+    }
+}
+
+// The usual calling convention: arguments go to [rb+1], [rb+2]... the return
+// address goes to [rb+0] and an unconditional jump enters the function.
+struct CallSite
+{
+    start: usize,
+    jump: usize,
+    target: Parameter,
+    // offset from rb to the instruction writing that argument
+    arguments: BTreeMap<i64, usize>
+}
+
+struct Function
+{
+    entry: usize,
+    // values reserved by the ARB the function starts with
+    frame: usize,
+    parameters: usize,
+    returns_value: bool,
+    body: Vec<usize>
+}
+
+#[derive(Debug, Clone)]
+enum StepKind
+{
+    Statement(String),
+    Jump {condition: Option<Condition>, target: usize}
+}
+
+// one or more instructions that turn into a single statement or jump
+#[derive(Debug, Clone)]
+struct Step
+{
+    // instructions from start up to address have no effect, jumping to any of them is the same
+    start: usize,
+    address: usize,
+    next: usize,
+    kind: StepKind
+}
+
+enum Node
+{
+    Line {address: usize, text: String},
+    If {address: usize, condition: String, then: Vec<Node>, otherwise: Vec<Node>},
+    While {address: usize, condition: String, body: Vec<Node>},
+    DoWhile {address: usize, condition: String, body: Vec<Node>}
+}
+
+impl Node
+{
+    fn address(&self) -> usize
+    {
+        match self
+        {
+            Self::Line {address, ..} | Self::If {address, ..} | Self::While {address, ..} | Self::DoWhile {address, ..} => *address
+        }
+    }
+}
+
+// steps that continue and break go to in the innermost loop
+#[derive(Clone, Copy)]
+struct LoopTargets
+{
+    next_iteration: usize,
+    exit: usize
+}
+
+// the step a jump to the target ends up at, steps.len() past the last one
+fn step_at(steps: &[Step], target: usize) -> Option<usize>
+{
+    if steps.last().is_some_and(|s| s.next == target)
+    {
+        return Some(steps.len());
+    }
+
+    return steps.iter().position(|s| (s.start..=s.address).contains(&target));
+}
+
+struct Decompiler
+{
+    code: BTreeMap<usize, Instruction>,
+    // address of the instruction each address of code belongs to
+    code_owner: BTreeMap<usize, usize>,
+    calls: BTreeMap<usize, CallSite>,
+    functions: BTreeMap<usize, Function>
+}
+
+impl Decompiler
+{
+    fn new(program: &Program) -> Self
+    {
+        let code = find_code(program);
+        let code_owner = code.values().flat_map(|i| (i.address..i.next_address()).map(move |a| (a, i.address))).collect();
+
+        let mut decompiler = Self
+        {
+            code,
+            code_owner,
+            calls: BTreeMap::new(),
+            functions: BTreeMap::new()
+        };
+
+        decompiler.find_calls();
+        decompiler.find_functions();
+
+        return decompiler;
+    }
+
+    fn previous(&self, instruction: &Instruction) -> Option<&Instruction>
+    {
+        self.code.range(..instruction.address).next_back()
+            .map(|(_, i)| i)
+            .filter(|i| i.next_address() == instruction.address)
+    }
+
+    fn find_calls(&mut self)
+    {
+        for jump in self.code.values().filter(|i| i.is_unconditional_jump())
+        {
+            let mut start = jump.address;
+            let mut return_address = None;
+            let mut arguments = BTreeMap::new();
+            let mut current = jump;
+
+            while let Some(previous) = self.previous(current)
+            {
+                let offset = match destination(previous)
+                {
+                    Some(d) if d.mode == ParameterMode::Relative && d.value >= 0 => d.value,
+                    _ => break
+                };
+
+                if offset == 0
+                {
+                    return_address.get_or_insert(constant_result(previous));
+                }
+                else
+                {
+                    arguments.entry(offset).or_insert(previous.address);
+                }
+
+                start = previous.address;
+                current = previous;
+            }
+
+            if return_address == Some(Some(jump.next_address() as i64))
+            {
+                let target = parameters(jump)[1];
+                self.calls.insert(jump.address, CallSite {start, jump: jump.address, target, arguments});
+            }
+        }
+    }
+
+    fn find_functions(&mut self)
+    {
+        let mut entries: BTreeSet<usize> = self.calls.values()
+            .filter_map(|c| c.target.constant())
+            .filter(|&t| t >= 0 && self.code.contains_key(&(t as usize)))
+            .map(|t| t as usize)
+            .collect();
+        entries.insert(0);
+
+        for entry in entries
+        {
+            if !self.code.contains_key(&entry)
+            {
+                continue;
+            }
+
+            let frame = match (self.code[&entry].opcode, parameters(&self.code[&entry]).first())
+            {
+                (Opcode::RelativeBaseOffset(_), Some(&Parameter {mode: ParameterMode::Immediate, value})) if value > 0 => value as usize,
+                _ => 0
+            };
+
+            let call_sites: Vec<&CallSite> = self.calls.values().filter(|c| c.target.constant() == Some(entry as i64)).collect();
+
+            let parameters = call_sites.iter()
+                .filter_map(|c| c.arguments.keys().next_back())
+                .map(|&offset| offset as usize)
+                .max()
+                .unwrap_or(0)
+                .min(frame.saturating_sub(1));
+
+            // the result is left where the first argument was
+            let returns_value = call_sites.iter().any(|c| {
+                match self.code.get(&self.code[&c.jump].next_address())
+                {
+                    Some(next) => sources(next).iter().any(|p| p.is_relative(1)),
+                    None => false
+                }
+            });
+
+            let body = self.function_body(entry);
+            self.functions.insert(entry, Function {entry, frame, parameters, returns_value, body});
+        }
+    }
+
+    // code reached from the entry without entering called functions
+    fn function_body(&self, entry: usize) -> Vec<usize>
+    {
+        let mut body = BTreeSet::new();
+        let mut pending = vec![entry];
+
+        while let Some(address) = pending.pop()
+        {
+            let instruction = match self.code.get(&address)
+            {
+                Some(i) if body.insert(address) => i,
+                _ => continue
+            };
+
+            if self.calls.contains_key(&address)
+            {
+                pending.push(instruction.next_address());
+                continue;
+            }
+
+            if is_return(instruction)
+            {
+                continue;
+            }
+
+            if instruction.falls_through()
+            {
+                pending.push(instruction.next_address());
+            }
+
+            if let Some(target) = instruction.static_jump_target()
+            {
+                pending.push(target);
+            }
+        }
+
+        return body.into_iter().collect();
+    }
+
+    // Whether a position written by a comparison is only there to feed the
+    // jump after it: every path from the jump writes it again before reading it.
+    fn is_condition_temp(&self, jump: &Instruction, position: usize) -> bool
+    {
+        let temp = Parameter {mode: ParameterMode::Position, value: position as i64};
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![jump.next_address()];
+        pending.extend(jump.static_jump_target());
+
+        while let Some(address) = pending.pop()
+        {
+            let instruction = match self.code.get(&address)
+            {
+                Some(i) if visited.insert(address) => i,
+                _ => continue
+            };
+
+            if sources(instruction).contains(&temp)
+            {
+                return false;
+            }
+
+            if destination(instruction) == Some(temp)
+            {
+                continue;
+            }
+
+            if instruction.falls_through()
+            {
+                pending.push(instruction.next_address());
+            }
+
+            pending.extend(instruction.static_jump_target());
+        }
+
+        return true;
+    }
+
+    fn function_name(&self, entry: usize) -> String
+    {
+        if entry == 0 {"main".to_string()} else {format!("f{:04}", entry)}
+    }
+
+    fn slot_name(&self, function: &Function, slot: usize) -> String
+    {
+        if slot <= function.parameters {format!("arg{}", slot)} else {format!("local{}", slot - function.parameters)}
+    }
+
+    fn operand(&self, function: &Function, parameter: Parameter) -> String
+    {
+        let frame = function.frame as i64;
+
+        match parameter.mode
+        {
+            ParameterMode::Immediate => parameter.value.to_string(),
+            ParameterMode::Position => format!("mem[{}]", parameter.value),
+            ParameterMode::Relative if parameter.value < 0 && parameter.value > -frame => {
+                self.slot_name(function, (frame + parameter.value) as usize)
+            },
+            ParameterMode::Relative => format!("rb[{}]", parameter.value)
+        }
+    }
+
+    fn expression(&self, function: &Function, instruction: &Instruction) -> String
+    {
+        let parameters = parameters(instruction);
+        let (a, b) = (parameters[0], parameters[1]);
+        let (x, y) = (self.operand(function, a), self.operand(function, b));
+
+        if let Some(value) = constant_result(instruction)
+        {
+            return value.to_string();
+        }
+
+        match instruction.opcode
+        {
+            Opcode::Add(..) => match (a.constant(), b.constant())
+            {
+                (Some(0), _) => y,
+                (_, Some(0)) => x,
+                (_, Some(c)) if c < 0 => format!("{} - {}", x, c.unsigned_abs()),
+                (Some(c), _) if c < 0 => format!("{} - {}", y, c.unsigned_abs()),
+                _ => format!("{} + {}", x, y)
+            },
+            Opcode::Mul(..) => match (a.constant(), b.constant())
+            {
+                (Some(0), _) | (_, Some(0)) => "0".to_string(),
+                (Some(1), _) => y,
+                (_, Some(1)) => x,
+                (Some(-1), _) => format!("-{}", y),
+                (_, Some(-1)) => format!("-{}", x),
+                _ => format!("{} * {}", x, y)
+            },
+            Opcode::LessThan(..) => format!("{} < {}", x, y),
+            _ => format!("{} == {}", x, y)
+        }
+    }
+
+    fn assignment(&self, function: &Function, instruction: &Instruction) -> String
+    {
+        let target = destination(instruction).unwrap();
+        let name = self.operand(function, target);
+
+        let mut text = match instruction.opcode
+        {
+            Opcode::Input(_) => format!("{} = input();", name),
+            Opcode::Add(..) if copied(instruction).is_none() && sources(instruction).contains(&target) => {
+                let parameters = parameters(instruction);
+                let other = if parameters[0] == target {parameters[1]} else {parameters[0]};
+
+                match other.constant()
+                {
+                    Some(c) if c < 0 => format!("{} -= {};", name, c.unsigned_abs()),
+                    _ => format!("{} += {};", name, self.operand(function, other))
+                }
+            },
+            _ => format!("{} = {};", name, self.expression(function, instruction))
+        };
+
+        if target.mode == ParameterMode::Position && target.value >= 0
+        {
+            if let Some(owner) = self.code_owner.get(&(target.value as usize))
+            {
+                text.push_str(&format!(" // modifies the instruction at {:04}", owner));
+            }
+        }
+
+        return text;
+    }
+
+    fn call(&self, function: &Function, call: &CallSite, result: Option<&Instruction>) -> String
+    {
+        let callee = match call.target.constant()
+        {
+            Some(entry) if entry >= 0 && self.functions.contains_key(&(entry as usize)) => self.function_name(entry as usize),
+            _ => format!("(*{})", self.operand(function, call.target))
+        };
+
+        let count = call.arguments.keys().next_back().copied().unwrap_or(0);
+        let arguments: Vec<String> = (1..=count).map(|offset| {
+            match call.arguments.get(&offset)
+            {
+                Some(address) => match copied(&self.code[address])
+                {
+                    Some(value) => self.operand(function, value),
+                    None => self.expression(function, &self.code[address])
+                },
+                None => "?".to_string()
+            }
+        }).collect();
+
+        let call_text = format!("{}({})", callee, arguments.join(", "));
+
+        match result
+        {
+            Some(copy) => format!("{} = {};", self.operand(function, destination(copy).unwrap()), call_text),
+            None => format!("{};", call_text)
+        }
+    }
+
+    fn returned(&self, function: &Function) -> String
+    {
+        if function.returns_value && function.frame > 1
+        {
+            return format!("return {};", self.slot_name(function, 1));
+        }
+
+        return "return;".to_string();
+    }
+
+    // condition under which a jump is taken
+    fn jump_condition(&self, function: &Function, jump: &Instruction, comparison: Option<&Instruction>) -> Option<Condition>
+    {
+        if jump.is_unconditional_jump()
+        {
+            return None;
+        }
+
+        let jumps_if_true = matches!(jump.opcode, Opcode::JumpIfTrue(..));
+
+        let condition = match comparison
+        {
+            Some(comparison) => {
+                let parameters = parameters(comparison);
+                Condition
+                {
+                    left: self.operand(function, parameters[0]),
+                    operator: if matches!(comparison.opcode, Opcode::LessThan(..)) {"<"} else {"=="},
+                    right: self.operand(function, parameters[1])
+                }
+            },
+            None => Condition
+            {
+                left: self.operand(function, parameters(jump)[0]),
+                operator: "!=",
+                right: "0".to_string()
+            }
+        };
+
+        return Some(if jumps_if_true {condition} else {condition.negate()});
+    }
+
+    fn steps(&self, function: &Function) -> Vec<Step>
+    {
+        let body: BTreeSet<usize> = function.body.iter().copied().collect();
+        let next_in_body = |instruction: &Instruction| {
+            body.get(&instruction.next_address()).map(|a| &self.code[a])
+        };
+
+        let mut steps = Vec::new();
+        let mut consumed_until = 0;
+        let mut skipped = None;
+
+        for &address in &function.body
+        {
+            if address < consumed_until
+            {
+                continue;
+            }
+
+            let instruction = &self.code[&address];
+            let mut next = instruction.next_address();
+
+            let call = self.calls.values().find(|c| c.start == address && body.contains(&c.jump));
+
+            let kind = if let Some(call) = call
+            {
+                let jump = &self.code[&call.jump];
+                next = jump.next_address();
+
+                let result = next_in_body(jump).filter(|i| {
+                    copied(i).is_some_and(|p| p.is_relative(1)) && destination(i).is_some_and(|d| !d.is_relative(1))
+                });
+
+                if let Some(result) = result
+                {
+                    next = result.next_address();
+                }
+
+                StepKind::Statement(self.call(function, call, result))
+            }
+            else if address == function.entry && function.frame > 0
+            {
+                // the prologue, the frame shows up as arguments and locals
+                skipped.get_or_insert(address);
+                continue;
+            }
+            else if is_return(instruction)
+            {
+                StepKind::Statement(self.returned(function))
+            }
+            else if let Some(jump) = next_in_body(instruction).filter(|j| {
+                let parameter = parameters(instruction).first().copied();
+                matches!(instruction.opcode, Opcode::RelativeBaseOffset(_)) && is_return(j) &&
+                parameter == Some(Parameter {mode: ParameterMode::Immediate, value: -(function.frame as i64)})
+            })
+            {
+                next = jump.next_address();
+                StepKind::Statement(self.returned(function))
+            }
+            else if let Some(jump) = next_in_body(instruction).filter(|j| {
+                matches!(instruction.opcode, Opcode::LessThan(..) | Opcode::Equals(..)) && j.is_jump() &&
+                destination(instruction).is_some_and(|d| {
+                    d.mode == ParameterMode::Position && d.value >= 0 && parameters(j)[0] == d &&
+                    self.is_condition_temp(j, d.value as usize)
+                })
+            })
+            {
+                next = jump.next_address();
+                let condition = self.jump_condition(function, jump, Some(instruction));
+
+                match jump.static_jump_target()
+                {
+                    Some(target) => StepKind::Jump {condition, target},
+                    None => StepKind::Statement(self.dynamic_jump(function, jump, condition))
+                }
+            }
+            else if never_jumps(instruction) || copied(instruction).is_some_and(|p| Some(p) == destination(instruction))
+            {
+                skipped.get_or_insert(address);
+                continue;
+            }
+            else if instruction.is_jump()
+            {
+                let condition = self.jump_condition(function, instruction, None);
+
+                match instruction.static_jump_target()
+                {
+                    Some(target) => StepKind::Jump {condition, target},
+                    None => StepKind::Statement(self.dynamic_jump(function, instruction, condition))
+                }
+            }
+            else
+            {
+                let parameters = parameters(instruction);
+
+                StepKind::Statement(match instruction.opcode
+                {
+                    Opcode::Output(_) => format!("output({});", self.operand(function, parameters[0])),
+                    Opcode::RelativeBaseOffset(_) => match parameters[0].constant()
+                    {
+                        Some(c) if c < 0 => format!("rb -= {};", c.unsigned_abs()),
+                        _ => format!("rb += {};", self.operand(function, parameters[0]))
+                    },
+                    Opcode::Halt => "halt;".to_string(),
+                    _ => self.assignment(function, instruction)
+                })
+            };
+
+            consumed_until = next;
+            steps.push(Step {start: skipped.take().unwrap_or(address), address, next, kind});
+
+            let last = self.code.range(..next).next_back().map(|(_, i)| i).unwrap();
+            let falls_into_data = last.falls_through() && !is_return(last) && !self.code.contains_key(&next);
+
+            if falls_into_data
+            {
+                let note = format!("// runs into {:04}, which isn't code until the program writes it", next);
+                steps.push(Step {start: next, address: next, next, kind: StepKind::Statement(note)});
+            }
+        }
+
+        return steps;
+    }
+
+    fn dynamic_jump(&self, function: &Function, jump: &Instruction, condition: Option<Condition>) -> String
+    {
+        let target = self.operand(function, parameters(jump)[1]);
+
+        match condition
+        {
+            Some(condition) => format!("if ({}) goto *{};", condition.render(), target),
+            None => format!("goto *{};", target)
+        }
+    }
+
+    // Turns jumps into if/else, while and do/while where they nest properly,
+    // whatever doesn't fit stays a goto.
+    fn structure(&self, steps: &[Step], lo: usize, hi: usize, loop_targets: Option<LoopTargets>,
+                 skip_loop_at_start: bool, labels: &mut BTreeSet<usize>) -> Vec<Node>
+    {
+        let position = |target: usize, from: usize| step_at(steps, target).filter(|&j| j >= from && j <= hi);
+
+        let mut nodes = Vec::new();
+        let mut i = lo;
+
+        while i < hi
+        {
+            let step = &steps[i];
+
+            // a jump back to this step closes a loop, the furthest one is the outermost
+            let back_jump = (i + 1..hi).rev().find(|&k| matches!(steps[k].kind, StepKind::Jump {target, ..} if step_at(steps, target) == Some(i)));
+
+            // a loop that another loop starts inside of and ends after can't be nested, leave it to gotos
+            let back_jump = back_jump.filter(|&k| !(k + 1..hi).any(|s| {
+                matches!(steps[s].kind, StepKind::Jump {target, ..} if step_at(steps, target).is_some_and(|t| t > i && t <= k))
+            }));
+
+            if let (Some(k), false) = (back_jump, skip_loop_at_start && i == lo)
+            {
+                let exit_step = k + 1;
+
+                let node = match &steps[k].kind
+                {
+                    StepKind::Jump {condition: Some(condition), ..} => Node::DoWhile
+                    {
+                        address: step.address,
+                        condition: condition.render(),
+                        body: self.structure(steps, i, k, Some(LoopTargets {next_iteration: k, exit: exit_step}), true, labels)
+                    },
+                    _ => {
+                        let targets = Some(LoopTargets {next_iteration: i, exit: exit_step});
+
+                        match &step.kind
+                        {
+                            StepKind::Jump {condition: Some(condition), target} if step_at(steps, *target) == Some(exit_step) => Node::While
+                            {
+                                address: step.address,
+                                condition: condition.negate().render(),
+                                body: self.structure(steps, i + 1, k, targets, false, labels)
+                            },
+                            _ => Node::While
+                            {
+                                address: step.address,
+                                condition: "true".to_string(),
+                                body: self.structure(steps, i, k, targets, true, labels)
+                            }
+                        }
+                    }
+                };
+
+                nodes.push(node);
+                i = k + 1;
+                continue;
+            }
+
+            let (condition, target) = match &step.kind
+            {
+                StepKind::Statement(text) => {
+                    nodes.push(Node::Line {address: step.address, text: text.clone()});
+                    i += 1;
+                    continue;
+                },
+                StepKind::Jump {condition, target} => (condition, *target)
+            };
+
+            let forward = position(target, i + 1);
+
+            if let Some(j) = forward
+            {
+                match condition
+                {
+                    // jumping to the next step does nothing
+                    None if j == i + 1 => {
+                        i = j;
+                        continue;
+                    },
+                    Some(condition) => {
+                        let else_end = match steps.get(j - 1).map(|s| &s.kind)
+                        {
+                            Some(StepKind::Jump {condition: None, target: after}) if j > i + 1 => {
+                                position(*after, j).map(|m| (j - 1, m))
+                            },
+                            _ => None
+                        };
+
+                        let (then_end, next) = else_end.unwrap_or((j, j));
+
+                        // further jumps past the same code at the start of the body are more of the condition
+                        let mut taken = vec![condition.clone()];
+                        let mut then_start = i + 1;
+                        while then_start < then_end
+                        {
+                            match &steps[then_start].kind
+                            {
+                                StepKind::Jump {condition: Some(more), target: t} if *t == target => taken.push(more.clone()),
+                                _ => break
+                            }
+                            then_start += 1;
+                        }
+
+                        let then = self.structure(steps, then_start, then_end, loop_targets, false, labels);
+                        let otherwise = if next > j {self.structure(steps, j, next, loop_targets, false, labels)} else {vec![]};
+
+                        let node = if then.is_empty() && !otherwise.is_empty()
+                        {
+                            let taken: Vec<String> = taken.iter().map(|c| c.render()).collect();
+                            Node::If {address: step.address, condition: taken.join(" || "), then: otherwise, otherwise: vec![]}
+                        }
+                        else
+                        {
+                            let not_taken: Vec<String> = taken.iter().map(|c| c.negate().render()).collect();
+                            Node::If {address: step.address, condition: not_taken.join(" && "), then, otherwise}
+                        };
+
+                        nodes.push(node);
+
+                        i = next;
+                        continue;
+                    },
+                    _ => ()
+                }
+            }
+
+            let text = match (loop_targets, step_at(steps, target))
+            {
+                (Some(targets), Some(j)) if j == targets.next_iteration => "continue;".to_string(),
+                (Some(targets), Some(j)) if j == targets.exit => "break;".to_string(),
+                (_, j) => {
+                    // labels go on the first instruction that does something
+                    let label = j.and_then(|j| steps.get(j)).map_or(target, |s| s.address);
+                    labels.insert(label);
+                    format!("goto L{:04};", label)
+                }
+            };
+
+            let text = match condition
+            {
+                Some(condition) => format!("if ({}) {}", condition.render(), text),
+                None => text
+            };
+
+            nodes.push(Node::Line {address: step.address, text});
+            i += 1;
+        }
+
+        return nodes;
+    }
+
+    fn print(&self, nodes: &[Node], depth: usize, labels: &mut BTreeSet<usize>, out: &mut String)
+    {
+        let indent = INDENT.repeat(depth);
+
+        for node in nodes
+        {
+            if labels.remove(&node.address())
+            {
+                out.push_str(&format!("{}L{:04}:\n", indent, node.address()));
+            }
+
+            match node
+            {
+                Node::Line {text, ..} => out.push_str(&format!("{}{}\n", indent, text)),
+                Node::If {condition, then, otherwise, ..} => {
+                    out.push_str(&format!("{}if ({})\n{}{{\n", indent, condition, indent));
+                    self.print(then, depth + 1, labels, out);
+                    out.push_str(&format!("{}}}\n", indent));
+
+                    if !otherwise.is_empty()
+                    {
+                        out.push_str(&format!("{}else\n{}{{\n", indent, indent));
+                        self.print(otherwise, depth + 1, labels, out);
+                        out.push_str(&format!("{}}}\n", indent));
+                    }
+                },
+                Node::While {condition, body, ..} => {
+                    out.push_str(&format!("{}while ({})\n{}{{\n", indent, condition, indent));
+                    self.print(body, depth + 1, labels, out);
+                    out.push_str(&format!("{}}}\n", indent));
+                },
+                Node::DoWhile {condition, body, ..} => {
+                    out.push_str(&format!("{}do\n{}{{\n", indent, indent));
+                    self.print(body, depth + 1, labels, out);
+                    out.push_str(&format!("{}}}\n{}while ({});\n", indent, indent, condition));
+                }
+            }
+        }
+    }
+
+    fn function(&self, function: &Function) -> String
+    {
+        let arguments: Vec<String> = (1..=function.parameters).map(|slot| self.slot_name(function, slot)).collect();
+        let mut out = format!("fn {}({})\n{{\n", self.function_name(function.entry), arguments.join(", "));
+
+        let locals: Vec<String> = (function.parameters + 1..function.frame).map(|slot| self.slot_name(function, slot)).collect();
+        if !locals.is_empty()
+        {
+            out.push_str(&format!("{}var {};\n\n", INDENT, locals.join(", ")));
+        }
+
+        let steps = self.steps(function);
+        if !steps.is_empty()
+        {
+            let mut labels = BTreeSet::new();
+            let nodes = self.structure(&steps, 0, steps.len(), None, false, &mut labels);
+            self.print(&nodes, 1, &mut labels, &mut out);
+
+            // targets that ended up inside another statement or outside the function
+            for label in labels
+            {
+                out.push_str(&format!("{}// L{:04} is not a statement of this function\n", INDENT, label));
+            }
+        }
+
+        out.push_str("}\n");
+        return out;
+    }
+}
+
+// Pseudo-code for a program, one function for the code reached from address 0
+// and one for every target of the usual calling convention. Relative base
+// frames become arguments and locals, comparisons are folded into the jumps
+// they feed and jumps become if/else and loops where they nest. Names stay
+// close to the machine: mem[a] is a position, rb[o] a relative address
+// outside the current frame.
+pub fn decompile(program: &Program) -> String
+{
+    let decompiler = Decompiler::new(program);
+
+    let functions: Vec<String> = decompiler.functions.values().map(|f| decompiler.function(f)).collect();
+    return functions.join("\n");
+}
@@ -0,0 +1,259 @@
+use crate::opcode::{Opcode, ParameterMode};
+use crate::program::Program;
+use crate::word::Word;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+// number of values shown on a single .data line
+const DATA_PER_LINE: usize = 8;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Instruction<W = i64>
+{
+    pub(crate) address: usize,
+    pub(crate) opcode: Opcode,
+    pub(crate) parameters: Vec<W>
+}
+
+struct Operand<W>
+{
+    mode: ParameterMode,
+    value: W
+}
+
+impl<W: Word> Instruction<W>
+{
+    pub(crate) fn decode(program: &Program<W>, address: usize) -> Option<Self>
+    {
+        if address >= program.len()
+        {
+            return None;
+        }
+
+        let opcode = Opcode::from_intcode(program[address].to_i64()?)?;
+
+        if address + opcode.length() > program.len()
+        {
+            return None;
+        }
+
+        let parameters = (1..opcode.length()).map(|i| program.get(address + i)).collect();
+
+        return Some(Self {address, opcode, parameters});
+    }
+
+    pub(crate) fn next_address(&self) -> usize
+    {
+        return self.address + self.opcode.length();
+    }
+
+    // target of a jump that can be worked out without running the program
+    pub(crate) fn static_jump_target(&self) -> Option<usize>
+    {
+        match self.opcode
+        {
+            Opcode::JumpIfTrue(_, ParameterMode::Immediate) | Opcode::JumpIfFalse(_, ParameterMode::Immediate) => {
+                self.parameters[1].to_i64().filter(|&target| target >= 0).map(|target| target as usize)
+            },
+            _ => None
+        }
+    }
+
+    pub(crate) fn is_jump(&self) -> bool
+    {
+        matches!(self.opcode, Opcode::JumpIfTrue(..) | Opcode::JumpIfFalse(..))
+    }
+
+    // a jump whose condition is an immediate that always takes the branch
+    pub(crate) fn is_unconditional_jump(&self) -> bool
+    {
+        match self.opcode
+        {
+            Opcode::JumpIfTrue(ParameterMode::Immediate, _) => self.parameters[0] != W::from_i64(0),
+            Opcode::JumpIfFalse(ParameterMode::Immediate, _) => self.parameters[0] == W::from_i64(0),
+            _ => false
+        }
+    }
+
+    pub(crate) fn falls_through(&self) -> bool
+    {
+        return self.opcode != Opcode::Halt && !self.is_unconditional_jump();
+    }
+
+    fn operands(&self) -> Vec<Operand<W>>
+    {
+        self.opcode.parameter_modes().into_iter()
+            .zip(self.parameters.iter())
+            .map(|(mode, value)| Operand {mode, value: value.clone()})
+            .collect()
+    }
+}
+
+impl<W: Word> fmt::Display for Operand<W>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self.mode
+        {
+            ParameterMode::Position => write!(f, "[{}]", self.value),
+            ParameterMode::Immediate => write!(f, "#{}", self.value),
+            // Word has no negation, the sign is moved over as text
+            ParameterMode::Relative => match self.value.to_string().strip_prefix('-')
+            {
+                Some(magnitude) => write!(f, "[rb-{}]", magnitude),
+                None => write!(f, "[rb+{}]", self.value)
+            }
+        }
+    }
+}
+
+impl<W: Word> fmt::Display for Instruction<W>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let mut operands = self.operands();
+        let destination = if self.opcode.writes_last_parameter() {operands.pop()} else {None};
+
+        write!(f, "{}", self.opcode.mnemonic())?;
+
+        let sources: Vec<String> = operands.iter().map(|o| o.to_string()).collect();
+        if !sources.is_empty()
+        {
+            write!(f, " {}", sources.join(", "))?;
+        }
+
+        if let Some(d) = destination
+        {
+            write!(f, " -> {}", d)?;
+        }
+
+        return Ok(());
+    }
+}
+
+// Code is found by following execution from address 0 through fall throughs and
+// jumps with immediate targets. Return addresses pushed by the usual call idiom
+// (an immediate that points just past a jump) are followed as well, everything
+// that is never reached is treated as data.
+pub(crate) fn find_code(program: &Program) -> BTreeMap<usize, Instruction>
+{
+    return find_code_from(program, &[0]);
+}
+
+// like find_code, with extra entry points such as jump targets seen at run time
+pub(crate) fn find_code_from(program: &Program, entries: &[usize]) -> BTreeMap<usize, Instruction>
+{
+    let mut code = BTreeMap::new();
+    let mut claimed = BTreeSet::new();
+    let mut tried = BTreeSet::new();
+    // entries are popped from the end, so the first one is followed first
+    let mut pending: Vec<usize> = entries.iter().rev().copied().collect();
+
+    while !pending.is_empty()
+    {
+        while let Some(address) = pending.pop()
+        {
+            if !tried.insert(address) || claimed.contains(&address)
+            {
+                continue;
+            }
+
+            let instruction = match Instruction::decode(program, address)
+            {
+                Some(i) => i,
+                None => continue
+            };
+
+            if (address..instruction.next_address()).any(|a| claimed.contains(&a))
+            {
+                continue;
+            }
+
+            claimed.extend(address..instruction.next_address());
+
+            if instruction.falls_through()
+            {
+                pending.push(instruction.next_address());
+            }
+
+            if let Some(target) = instruction.static_jump_target()
+            {
+                pending.push(target);
+            }
+
+            code.insert(address, instruction);
+        }
+
+        let ends_of_jumps: BTreeSet<usize> = code.values()
+            .filter(|i| i.is_jump())
+            .map(|i| i.next_address())
+            .collect();
+
+        for instruction in code.values()
+        {
+            for operand in instruction.operands()
+            {
+                if operand.mode == ParameterMode::Immediate && operand.value >= 0
+                {
+                    let target = operand.value as usize;
+
+                    if ends_of_jumps.contains(&target) && !tried.contains(&target)
+                    {
+                        pending.push(target);
+                    }
+                }
+            }
+        }
+
+        pending.dedup();
+    }
+
+    return code;
+}
+
+pub fn disassemble(program: &Program) -> String
+{
+    let code = find_code(program);
+    let mut listing = String::new();
+    let mut address = 0;
+
+    while address < program.len()
+    {
+        if let Some(instruction) = code.get(&address)
+        {
+            if instruction.opcode.writes_immediate()
+            {
+                // kept as data so the listing still assembles, the instruction goes in a comment
+                let values: Vec<String> = (address..instruction.next_address()).map(|a| program[a].to_string()).collect();
+                listing.push_str(&format!("{:04}: .data {} ; {}\n", address, values.join(", "), instruction));
+            }
+            else
+            {
+                listing.push_str(&format!("{:04}: {}\n", address, instruction));
+            }
+
+            address = instruction.next_address();
+        }
+        else
+        {
+            let mut values = Vec::new();
+            while address + values.len() < program.len() &&
+                  values.len() < DATA_PER_LINE &&
+                  !code.contains_key(&(address + values.len()))
+            {
+                values.push(program[address + values.len()].to_string());
+            }
+
+            listing.push_str(&format!("{:04}: .data {}\n", address, values.join(", ")));
+            address += values.len();
+        }
+    }
+
+    return listing;
+}
+
+pub fn disassemble_instruction<W: Word>(program: &Program<W>, address: usize) -> Option<String>
+{
+    Instruction::decode(program, address).map(|i| i.to_string())
+}
@@ -0,0 +1,65 @@
+use crate::isa::IsaProfile;
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntcodeErrorKind
+{
+    InvalidOpcode,
+    NegativeAddress(i64),
+    TruncatedInstruction,
+    NegativeRelativeBase(i64),
+    MemoryLimitExceeded(usize),
+    Overflow,
+    AddressOutOfRange,
+    ImmediateWrite,
+    OpcodeNotInProfile(IsaProfile),
+    // the number of the parameter, counting from 1
+    ModeNotInProfile(IsaProfile, usize),
+    // an IoBackend failed, Computer::take_io_error has the details
+    Io
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntcodeError
+{
+    pub kind: IntcodeErrorKind,
+    pub instruction_pointer: usize,
+    pub intcode: i64,
+    pub relative_base_offset: usize
+}
+
+impl fmt::Display for IntcodeErrorKind
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::InvalidOpcode => write!(f, "Invalid Opcode"),
+            Self::NegativeAddress(address) => write!(f, "Malformed program - address {} out of bounds", address),
+            Self::TruncatedInstruction => write!(f, "Malformed program - opcode too short"),
+            Self::NegativeRelativeBase(value) => write!(f, "Malformed program - RelativeBaseOffset can only be set to positive numbers, got {}", value),
+            Self::MemoryLimitExceeded(address) => write!(f, "Memory limit exceeded - writing to address {} needs more memory than allowed", address),
+            Self::Overflow => write!(f, "Arithmetic overflow"),
+            Self::AddressOutOfRange => write!(f, "Malformed program - address doesn't fit in 64 bits"),
+            Self::ImmediateWrite => write!(f, "Malformed program - the parameter written to is in immediate mode"),
+            Self::OpcodeNotInProfile(profile) => write!(f, "Opcode not in the {} instruction set", profile),
+            Self::ModeNotInProfile(profile, parameter) => write!(f, "Mode of parameter {} not in the {} instruction set", parameter, profile),
+            Self::Io => write!(f, "I/O backend failed")
+        }
+    }
+}
+
+impl fmt::Display for IntcodeError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{} (address: {}, intcode: {}, relative base: {})",
+               self.kind,
+               self.instruction_pointer,
+               self.intcode,
+               self.relative_base_offset)
+    }
+}
+
+impl std::error::Error for IntcodeError {}
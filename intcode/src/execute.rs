@@ -0,0 +1,206 @@
+use crate::computer::Arithmetic;
+use crate::error::{IntcodeError, IntcodeErrorKind};
+use crate::opcode::{Opcode, ParameterMode};
+use crate::program::Program;
+use crate::word::Word;
+
+// what came of executing one instruction
+pub(crate) enum Executed<W>
+{
+    Running,
+    Output(W),
+    // the output couldn't be taken, the instruction pointer is still on OUT
+    OutputBlocked,
+    AwaitingInput,
+    Halted
+}
+
+// The state instructions work on. Computer executes instructions through
+// execute below whatever its Word, what it does around it (decoding,
+// instrumentation, where I/O goes) stays in Computer.
+pub(crate) trait Machine
+{
+    type Word: Word;
+
+    fn memory(&self) -> &Program<Self::Word>;
+    fn instruction_pointer(&self) -> usize;
+    fn set_instruction_pointer(&mut self, address: usize);
+    fn relative_base(&self) -> usize;
+    fn set_relative_base(&mut self, relative_base: usize);
+
+    fn arithmetic(&self) -> Arithmetic
+    {
+        return Arithmetic::Checked;
+    }
+
+    // going over the memory limit is a MemoryLimitExceeded error
+    fn write(&mut self, address: usize, value: Self::Word) -> Result<(), IntcodeError>;
+
+    // None leaves the machine waiting for input
+    fn input(&mut self) -> Result<Option<Self::Word>, IntcodeError>;
+
+    fn can_output(&self) -> bool
+    {
+        return true;
+    }
+
+    fn output(&mut self, value: Self::Word) -> Result<(), IntcodeError>;
+
+    // kept out of line, building errors everywhere made execute too big to inline its helpers
+    #[cold]
+    #[inline(never)]
+    fn error(&self, kind: IntcodeErrorKind) -> IntcodeError
+    {
+        IntcodeError
+        {
+            kind,
+            instruction_pointer: self.instruction_pointer(),
+            // an intcode too wide for i64 is reported as 0, it can't be a valid opcode anyway
+            intcode: self.memory().get(self.instruction_pointer()).to_i64().unwrap_or(0),
+            relative_base_offset: self.relative_base()
+        }
+    }
+}
+
+// The helpers below run for every parameter, as calls that return their
+// Result through memory they made the computer about 40% slower.
+#[inline(always)]
+fn address<M: Machine>(machine: &M, value: &M::Word) -> Result<usize, IntcodeError>
+{
+    match value.to_i64()
+    {
+        Some(address) if address < 0 => Err(machine.error(IntcodeErrorKind::NegativeAddress(address))),
+        Some(address) => Ok(address as usize),
+        None => Err(machine.error(IntcodeErrorKind::AddressOutOfRange))
+    }
+}
+
+// address a parameter refers to, an immediate is taken as a position here,
+// strict ISA profiles reject it when decoding
+#[inline(always)]
+fn destination<M: Machine>(machine: &M, mode: ParameterMode, offset: usize) -> Result<usize, IntcodeError>
+{
+    let parameter = machine.memory().get(machine.instruction_pointer() + offset);
+
+    match mode
+    {
+        ParameterMode::Position | ParameterMode::Immediate => address(machine, &parameter),
+        ParameterMode::Relative => match parameter.to_i64().and_then(|p| p.checked_add(machine.relative_base() as i64))
+        {
+            Some(relative) if relative < 0 => Err(machine.error(IntcodeErrorKind::NegativeAddress(relative))),
+            Some(relative) => Ok(relative as usize),
+            None => Err(machine.error(IntcodeErrorKind::AddressOutOfRange))
+        }
+    }
+}
+
+#[inline(always)]
+fn operand<M: Machine>(machine: &M, mode: ParameterMode, offset: usize) -> Result<M::Word, IntcodeError>
+{
+    match mode
+    {
+        ParameterMode::Immediate => Ok(machine.memory().get(machine.instruction_pointer() + offset)),
+        _ => Ok(machine.memory().get(destination(machine, mode, offset)?))
+    }
+}
+
+#[inline(always)]
+fn arithmetic<M: Machine>(machine: &M, a: &M::Word, b: &M::Word, multiply: bool) -> Result<M::Word, IntcodeError>
+{
+    match (machine.arithmetic(), multiply)
+    {
+        (Arithmetic::Wrapping, false) => Ok(a.wrapping_add(b)),
+        (Arithmetic::Wrapping, true) => Ok(a.wrapping_mul(b)),
+        (Arithmetic::Checked, false) => a.checked_add(b).ok_or_else(|| machine.error(IntcodeErrorKind::Overflow)),
+        (Arithmetic::Checked, true) => a.checked_mul(b).ok_or_else(|| machine.error(IntcodeErrorKind::Overflow))
+    }
+}
+
+// executes the decoded instruction at the instruction pointer
+#[inline]
+pub(crate) fn execute<M: Machine>(machine: &mut M, opcode: Opcode) -> Result<Executed<M::Word>, IntcodeError>
+{
+    let ip = machine.instruction_pointer();
+
+    if machine.memory().len().saturating_sub(ip) < opcode.length()
+    {
+        return Err(machine.error(IntcodeErrorKind::TruncatedInstruction));
+    }
+
+    match opcode
+    {
+        Opcode::Add(a, b, c) | Opcode::Mul(a, b, c) => {
+            let arg1 = operand(machine, a, 1)?;
+            let arg2 = operand(machine, b, 2)?;
+            let result_address = destination(machine, c, 3)?;
+
+            let result = arithmetic(machine, &arg1, &arg2, matches!(opcode, Opcode::Mul(..)))?;
+            machine.write(result_address, result)?;
+        },
+        Opcode::Input(a) => {
+            let destination_address = destination(machine, a, 1)?;
+
+            match machine.input()?
+            {
+                Some(value) => machine.write(destination_address, value)?,
+                None => return Ok(Executed::AwaitingInput)
+            }
+        },
+        Opcode::Output(a) => {
+            let value = operand(machine, a, 1)?;
+
+            // stops before the output so the program can go on once there's room
+            if !machine.can_output()
+            {
+                return Ok(Executed::OutputBlocked);
+            }
+
+            machine.output(value.clone())?;
+            machine.set_instruction_pointer(ip + 2);
+
+            return Ok(Executed::Output(value));
+        },
+        Opcode::JumpIfTrue(z, d) | Opcode::JumpIfFalse(z, d) => {
+            let condition = operand(machine, z, 1)?;
+            let target = operand(machine, d, 2)?;
+
+            // the target has to be valid even when the jump isn't taken
+            let target = address(machine, &target)?;
+
+            if (condition != M::Word::from_i64(0)) == matches!(opcode, Opcode::JumpIfTrue(..))
+            {
+                machine.set_instruction_pointer(target);
+                return Ok(Executed::Running);
+            }
+        },
+        Opcode::LessThan(a, b, c) | Opcode::Equals(a, b, c) => {
+            let arg1 = operand(machine, a, 1)?;
+            let arg2 = operand(machine, b, 2)?;
+            let result_address = destination(machine, c, 3)?;
+
+            let result = if matches!(opcode, Opcode::LessThan(..)) {arg1 < arg2} else {arg1 == arg2};
+            machine.write(result_address, M::Word::from_i64(result as i64))?;
+        },
+        Opcode::RelativeBaseOffset(a) => {
+            let adjustment = operand(machine, a, 1)?;
+
+            let new_value = match adjustment.to_i64().and_then(|a| (machine.relative_base() as i64).checked_add(a))
+            {
+                Some(value) => value,
+                None => return Err(machine.error(IntcodeErrorKind::AddressOutOfRange))
+            };
+
+            if new_value < 0
+            {
+                return Err(machine.error(IntcodeErrorKind::NegativeRelativeBase(new_value)));
+            }
+
+            machine.set_relative_base(new_value as usize);
+        },
+        Opcode::Halt => return Ok(Executed::Halted)
+    }
+
+    machine.set_instruction_pointer(ip + opcode.length());
+
+    return Ok(Executed::Running);
+}
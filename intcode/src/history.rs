@@ -0,0 +1,63 @@
+use crate::computer::MemoryWrite;
+
+use std::collections::VecDeque;
+
+// everything needed to undo a single step
+pub(crate) struct UndoRecord<W>
+{
+    pub(crate) instruction_pointer: usize,
+    pub(crate) relative_base_offset: usize,
+    pub(crate) memory_len: usize,
+    pub(crate) write: Option<MemoryWrite<W>>,
+    pub(crate) consumed_input: Option<W>,
+    pub(crate) produced_output: bool
+}
+
+// undo log of the most recent steps, the oldest ones are dropped past the limit
+pub(crate) struct History<W>
+{
+    records: VecDeque<UndoRecord<W>>,
+    limit: usize
+}
+
+impl<W> History<W>
+{
+    pub(crate) fn new(limit: usize) -> Self
+    {
+        Self
+        {
+            records: VecDeque::new(),
+            limit
+        }
+    }
+
+    pub(crate) fn push(&mut self, record: UndoRecord<W>)
+    {
+        if self.limit == 0
+        {
+            return;
+        }
+
+        if self.records.len() == self.limit
+        {
+            self.records.pop_front();
+        }
+
+        self.records.push_back(record);
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<UndoRecord<W>>
+    {
+        self.records.pop_back()
+    }
+
+    pub(crate) fn len(&self) -> usize
+    {
+        self.records.len()
+    }
+
+    pub(crate) fn clear(&mut self)
+    {
+        self.records.clear();
+    }
+}
@@ -0,0 +1,209 @@
+use crate::word::Word;
+
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{stdin, stdout, Write};
+
+pub(crate) type InputFn<'a, W> = Box<dyn FnMut() -> Option<W> + 'a>;
+pub(crate) type OutputFn<'a, W> = Box<dyn FnMut(W) + 'a>;
+
+// A FIFO of values shared between whoever holds a clone, values are pushed
+// at the back and read from the front. A bounded queue refuses values once
+// it's full, a computer writing to one stops with RunState::OutputBlocked
+// until there's room again.
+pub struct IOQueue<W = i64>(Rc<RefCell<Queue<W>>>);
+
+struct Queue<W>
+{
+    values: VecDeque<W>,
+    capacity: Option<usize>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IOQueueError<W = i64>
+{
+    // the value that didn't fit
+    Full(W),
+    NotANumber(String)
+}
+
+impl<W: Word> fmt::Display for IOQueueError<W>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::Full(value) => write!(f, "Queue full, can't add {}", value),
+            Self::NotANumber(text) => write!(f, "Not a number: {}", text)
+        }
+    }
+}
+
+impl<W: Word> std::error::Error for IOQueueError<W> {}
+
+impl<W> Default for IOQueue<W>
+{
+    fn default() -> Self
+    {
+        Self(Rc::new(RefCell::new(Queue {values: VecDeque::new(), capacity: None})))
+    }
+}
+
+impl IOQueue
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+}
+
+impl<W: Word> IOQueue<W>
+{
+    // a queue that holds at most capacity values
+    pub fn bounded(capacity: usize) -> Self
+    {
+        Self(Rc::new(RefCell::new(Queue {values: VecDeque::new(), capacity: Some(capacity)})))
+    }
+
+    pub fn capacity(&self) -> Option<usize>
+    {
+        return self.0.borrow().capacity;
+    }
+
+    pub fn len(&self) -> usize
+    {
+        return self.0.borrow().values.len();
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        return self.0.borrow().values.is_empty();
+    }
+
+    pub fn is_full(&self) -> bool
+    {
+        let queue = self.0.borrow();
+        return queue.capacity.is_some_and(|capacity| queue.values.len() >= capacity);
+    }
+
+    pub fn push_back(&self, value: W) -> Result<(), IOQueueError<W>>
+    {
+        if self.is_full()
+        {
+            return Err(IOQueueError::Full(value));
+        }
+
+        self.0.borrow_mut().values.push_back(value);
+        return Ok(());
+    }
+
+    // puts a value in front of everything queued, so it's read next
+    pub fn push_front(&self, value: W) -> Result<(), IOQueueError<W>>
+    {
+        if self.is_full()
+        {
+            return Err(IOQueueError::Full(value));
+        }
+
+        self.0.borrow_mut().values.push_front(value);
+        return Ok(());
+    }
+
+    pub fn pop_front(&self) -> Option<W>
+    {
+        return self.0.borrow_mut().values.pop_front();
+    }
+
+    // takes back the value pushed last
+    pub fn pop_back(&self) -> Option<W>
+    {
+        return self.0.borrow_mut().values.pop_back();
+    }
+
+    pub fn front(&self) -> Option<W>
+    {
+        return self.0.borrow().values.front().cloned();
+    }
+
+    pub fn back(&self) -> Option<W>
+    {
+        return self.0.borrow().values.back().cloned();
+    }
+
+    pub fn clear(&self)
+    {
+        self.0.borrow_mut().values.clear();
+    }
+
+    // queued values, oldest first
+    pub fn values(&self) -> Vec<W>
+    {
+        return self.0.borrow().values.iter().cloned().collect();
+    }
+
+    // Parses a number the way it's typed, surrounding whitespace is ignored.
+    pub fn push_back_str(&self, text: &str) -> Result<(), IOQueueError<W>>
+    {
+        match text.trim().parse::<W>()
+        {
+            Ok(value) => self.push_back(value),
+            Err(_) => Err(IOQueueError::NotANumber(text.to_string()))
+        }
+    }
+
+    // queued values as text, oldest first
+    pub fn to_strings(&self) -> Vec<String>
+    {
+        return self.0.borrow().values.iter().map(|v| v.to_string()).collect();
+    }
+
+    // Replaces the queue contents with values, oldest first. Used to restore
+    // and undo, so the capacity isn't checked.
+    pub(crate) fn replace(&self, values: &[W])
+    {
+        let mut queue = self.0.borrow_mut();
+        queue.values.clear();
+        queue.values.extend(values.iter().cloned());
+    }
+
+    // puts back an input that was stepped back over, see replace
+    pub(crate) fn unread(&self, value: W)
+    {
+        self.0.borrow_mut().values.push_front(value);
+    }
+}
+
+impl<W> Clone for IOQueue<W> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<W: Word> Iterator for IOQueue<W> {
+    type Item = W;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pop_front()
+    }
+}
+
+pub(crate) fn stdin_input<W: Word>() -> Option<W>
+{
+    print!("Input Requested: ");
+    let _ = stdout().flush();
+    let mut input = String::new();
+    stdin().read_line(&mut input).unwrap();
+
+    match input.trim_end().parse::<W>()
+    {
+        Ok(value) => return Some(value),
+        Err(_) => panic!("Not a number: {}", input.trim_end())
+    }
+}
+
+pub(crate) fn stdout_output<W: Word>(output: W)
+{
+    println!("{}", output);
+}
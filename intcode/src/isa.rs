@@ -0,0 +1,113 @@
+use crate::disassembler::find_code;
+use crate::error::{IntcodeError, IntcodeErrorKind};
+use crate::opcode::{Opcode, ParameterMode};
+use crate::program::Program;
+
+use std::fmt;
+
+// The instruction set as it grew over the puzzles. A Computer with a strict
+// profile rejects anything the profile doesn't have, see Computer::set_strict_isa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IsaProfile
+{
+    // ADD, MUL and HALT, position mode only
+    Day2,
+    // adds I/O, jumps and comparisons, and immediate mode
+    Day5,
+    // adds ARB and relative mode
+    #[default]
+    Full
+}
+
+impl IsaProfile
+{
+    // day2, day5, day9 or full
+    pub fn from_name(name: &str) -> Option<Self>
+    {
+        match name.to_ascii_lowercase().as_str()
+        {
+            "day2" => Some(Self::Day2),
+            "day5" => Some(Self::Day5),
+            "day9" | "full" => Some(Self::Full),
+            _ => None
+        }
+    }
+
+    fn has_opcode(&self, opcode: &Opcode) -> bool
+    {
+        match self
+        {
+            Self::Day2 => matches!(opcode, Opcode::Add(..) | Opcode::Mul(..) | Opcode::Halt),
+            Self::Day5 => !matches!(opcode, Opcode::RelativeBaseOffset(_)),
+            Self::Full => true
+        }
+    }
+
+    fn has_mode(&self, mode: ParameterMode) -> bool
+    {
+        match self
+        {
+            Self::Day2 => mode == ParameterMode::Position,
+            Self::Day5 => mode != ParameterMode::Relative,
+            Self::Full => true
+        }
+    }
+
+    // whether the instruction set has the opcode with its parameter modes
+    pub(crate) fn check(&self, opcode: &Opcode) -> Result<(), IntcodeErrorKind>
+    {
+        if !self.has_opcode(opcode)
+        {
+            return Err(IntcodeErrorKind::OpcodeNotInProfile(*self));
+        }
+
+        let modes = opcode.parameter_modes();
+
+        if let Some(position) = modes.iter().position(|&m| !self.has_mode(m))
+        {
+            return Err(IntcodeErrorKind::ModeNotInProfile(*self, position + 1));
+        }
+
+        // no instruction set ever allowed writing to an immediate
+        if opcode.writes_immediate()
+        {
+            return Err(IntcodeErrorKind::ImmediateWrite);
+        }
+
+        return Ok(());
+    }
+
+    // Checks the code the disassembler can find without running the program,
+    // code the program writes for itself only shows up when it runs.
+    pub fn validate(&self, program: &Program) -> Result<(), IntcodeError>
+    {
+        for instruction in find_code(program).values()
+        {
+            if let Err(kind) = self.check(&instruction.opcode)
+            {
+                return Err(IntcodeError
+                {
+                    kind,
+                    instruction_pointer: instruction.address,
+                    intcode: program.get(instruction.address),
+                    relative_base_offset: 0
+                });
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+impl fmt::Display for IsaProfile
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::Day2 => write!(f, "day 2"),
+            Self::Day5 => write!(f, "day 5"),
+            Self::Full => write!(f, "full")
+        }
+    }
+}
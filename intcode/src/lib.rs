@@ -0,0 +1,73 @@
+#![allow(clippy::needless_return)]
+
+mod assembler;
+pub use assembler::*;
+
+mod backend;
+pub use backend::*;
+
+mod cfg;
+pub use cfg::{Block, ControlFlowGraph, EdgeKind};
+
+mod channel;
+pub use channel::ChannelComputer;
+
+mod computer;
+pub use computer::*;
+
+mod debugger;
+pub use debugger::*;
+
+mod decompiler;
+pub use decompiler::decompile;
+
+mod disassembler;
+pub use disassembler::{disassemble, disassemble_instruction};
+
+mod error;
+pub use error::*;
+
+mod execute;
+
+mod history;
+
+mod io;
+pub use io::*;
+
+mod isa;
+pub use isa::IsaProfile;
+
+mod limits;
+pub use limits::{Limit, LimitedRun, RunLimits};
+
+mod network;
+pub use network::{Network, NetworkError, NetworkState, Topology};
+
+mod opcode;
+
+mod outputs;
+pub use outputs::Outputs;
+
+mod phase_search;
+pub use phase_search::{search_phases, PhaseSearch};
+
+mod profile;
+pub use profile::{BasicBlock, HotLoop, Profiler};
+
+mod program;
+pub use program::*;
+
+mod session;
+pub use session::{ReplayError, Session, SessionError, SessionEvent};
+
+mod snapshot;
+pub use snapshot::{Snapshot, SnapshotError};
+
+mod solver;
+pub use solver::{Solution, SolveMethod, Solver};
+
+mod trace;
+pub use trace::Tracer;
+
+mod word;
+pub use word::Word;
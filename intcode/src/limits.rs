@@ -0,0 +1,93 @@
+use crate::computer::RunState;
+use crate::error::IntcodeError;
+use crate::word::Word;
+
+use std::time::{Duration, Instant};
+
+// the deadline is only checked every this many instructions, reading the clock isn't free
+const DEADLINE_CHECK_INTERVAL: u64 = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RunLimits
+{
+    pub(crate) max_instructions: Option<u64>,
+    pub(crate) deadline: Option<Instant>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit
+{
+    Instructions,
+    Deadline
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitedRun<W = i64>
+{
+    // the program halted or needs input, like Computer::run
+    Finished(RunState<W>),
+    // stopped before executing the instruction at instruction_pointer
+    LimitExceeded {limit: Limit, instruction_pointer: usize, executed: u64}
+}
+
+impl RunLimits
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn with_max_instructions(mut self, max_instructions: u64) -> Self
+    {
+        self.max_instructions = Some(max_instructions);
+        return self;
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self
+    {
+        self.deadline = Some(deadline);
+        return self;
+    }
+
+    // deadline this long from now
+    pub fn with_timeout(self, timeout: Duration) -> Self
+    {
+        return self.with_deadline(Instant::now() + timeout);
+    }
+}
+
+// Steps a machine until it halts or needs input, or until it has executed
+// max_instructions or the deadline has passed, whichever comes first.
+pub(crate) fn run_limited<M, W: Word>(machine: &mut M, limits: &RunLimits,
+                                      step: fn(&mut M) -> Result<RunState<W>, IntcodeError>,
+                                      instruction_pointer: fn(&M) -> usize) -> Result<LimitedRun<W>, IntcodeError>
+{
+    let mut executed = 0;
+
+    loop
+    {
+        let limit = if limits.max_instructions.is_some_and(|max| executed >= max)
+        {
+            Some(Limit::Instructions)
+        }
+        else if executed % DEADLINE_CHECK_INTERVAL == 0 && limits.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Some(Limit::Deadline)
+        }
+        else
+        {
+            None
+        };
+
+        if let Some(limit) = limit
+        {
+            return Ok(LimitedRun::LimitExceeded {limit, instruction_pointer: instruction_pointer(machine), executed});
+        }
+
+        match step(machine)?
+        {
+            RunState::Running | RunState::ProducedOutput(_) => executed += 1,
+            state => return Ok(LimitedRun::Finished(state))
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use crate::computer::{Computer, RunState};
+use crate::error::IntcodeError;
+use crate::io::IOQueue;
+use crate::program::Program;
+use crate::word::Word;
+
+use std::fmt;
+
+// how the outputs of the machines in a network feed the inputs of the others
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Topology
+{
+    // every machine feeds the next one, the last one's output leaves the network
+    Chain,
+    // like a chain, but the last machine feeds the first again
+    Ring,
+    // directed (from, to) edges, outputs are copied to every target and
+    // inputs from several machines are interleaved in the order they were produced
+    Graph(Vec<(usize, usize)>)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkState
+{
+    Halted,
+    // every machine that is still running waits for input nobody will produce
+    Deadlocked(Vec<usize>)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError
+{
+    // a machine stopped with an error
+    Machine {machine: usize, error: IntcodeError},
+    // an edge of a Topology::Graph refers to a machine the network doesn't have
+    InvalidEdge {from: usize, to: usize}
+}
+
+impl fmt::Display for NetworkError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::Machine {machine, error} => write!(f, "machine {}: {}", machine, error),
+            Self::InvalidEdge {from, to} => write!(f, "edge {} -> {} refers to a machine that doesn't exist", from, to)
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+pub struct Network<'a, W = i64>
+{
+    machines: Vec<Computer<'a, W>>,
+    inputs: Vec<IOQueue<W>>,
+    outputs: Vec<IOQueue<W>>,
+    targets: Vec<Vec<usize>>,
+    last_outputs: Vec<Option<W>>,
+    // outputs of machines without targets
+    unrouted: Vec<Vec<W>>
+}
+
+impl<'a> Network<'a>
+{
+    // count copies of the same program, Network::new takes computers with any Word
+    pub fn from_program(program: &Program, count: usize, topology: Topology) -> Result<Self, NetworkError>
+    {
+        let machines = (0..count).map(|_| {
+            let mut computer = Computer::new();
+            computer.load_program(program);
+            computer
+        }).collect();
+
+        return Self::new(machines, topology);
+    }
+}
+
+impl<'a, W: Word> Network<'a, W>
+{
+    // Takes over the I/O of the machines, anything they were connected to before is replaced.
+    pub fn new(mut machines: Vec<Computer<'a, W>>, topology: Topology) -> Result<Self, NetworkError>
+    {
+        let count = machines.len();
+
+        let targets = match topology
+        {
+            Topology::Chain => (0..count).map(|i| if i + 1 < count {vec![i + 1]} else {vec![]}).collect(),
+            Topology::Ring => (0..count).map(|i| vec![(i + 1) % count]).collect(),
+            Topology::Graph(edges) => {
+                let mut targets = vec![vec![]; count];
+                for (from, to) in edges
+                {
+                    if from >= count || to >= count
+                    {
+                        return Err(NetworkError::InvalidEdge {from, to});
+                    }
+
+                    targets[from].push(to);
+                }
+                targets
+            }
+        };
+
+        let inputs: Vec<IOQueue<W>> = (0..count).map(|_| IOQueue::default()).collect();
+        let outputs: Vec<IOQueue<W>> = (0..count).map(|_| IOQueue::default()).collect();
+
+        for (i, machine) in machines.iter_mut().enumerate()
+        {
+            machine.set_input_queue(&inputs[i]);
+            machine.set_output_queue(&outputs[i]);
+        }
+
+        return Ok(Self
+        {
+            machines,
+            inputs,
+            outputs,
+            targets,
+            last_outputs: vec![None; count],
+            unrouted: vec![vec![]; count]
+        });
+    }
+
+    pub fn len(&self) -> usize
+    {
+        return self.machines.len();
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        return self.machines.is_empty();
+    }
+
+    pub fn machine(&self, machine: usize) -> &Computer<'a, W>
+    {
+        return &self.machines[machine];
+    }
+
+    // queues input for a machine from outside the network
+    pub fn send(&self, machine: usize, value: W)
+    {
+        self.inputs[machine].push_back(value).unwrap();
+    }
+
+    // the most recent value a machine produced, wherever it went
+    pub fn last_output(&self, machine: usize) -> Option<W>
+    {
+        return self.last_outputs[machine].clone();
+    }
+
+    // outputs of a machine without targets since the last call
+    pub fn take_outputs(&mut self, machine: usize) -> Vec<W>
+    {
+        return std::mem::take(&mut self.unrouted[machine]);
+    }
+
+    // Runs the machines round-robin, each one until it halts or waits for
+    // input, until all have halted or a whole round passes without progress.
+    pub fn run(&mut self) -> Result<NetworkState, NetworkError>
+    {
+        loop
+        {
+            let mut progressed = false;
+            let mut starved = Vec::new();
+
+            for machine in 0..self.machines.len()
+            {
+                if self.machines[machine].is_halted()
+                {
+                    continue;
+                }
+
+                // a machine can loop back to the instruction it started on, so
+                // progress is counted in instructions rather than addresses
+                let executed = self.machines[machine].instructions_executed();
+                let state = self.machines[machine].run().map_err(|error| NetworkError::Machine {machine, error})?;
+
+                progressed |= self.machines[machine].instructions_executed() != executed;
+                self.route(machine);
+
+                if state == RunState::AwaitingInput
+                {
+                    starved.push(machine);
+                }
+            }
+
+            if starved.is_empty()
+            {
+                return Ok(NetworkState::Halted);
+            }
+
+            // input routed to a machine after its turn keeps the network going
+            if !progressed && starved.iter().all(|&machine| self.inputs[machine].is_empty())
+            {
+                return Ok(NetworkState::Deadlocked(starved));
+            }
+        }
+    }
+
+    fn route(&mut self, machine: usize)
+    {
+        while let Some(value) = self.outputs[machine].pop_front()
+        {
+            self.last_outputs[machine] = Some(value.clone());
+
+            if self.targets[machine].is_empty()
+            {
+                self.unrouted[machine].push(value.clone());
+            }
+
+            for &target in &self.targets[machine]
+            {
+                self.inputs[target].push_back(value.clone()).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+pub(crate) enum OpcodeType
+{
+    Add,
+    Mul,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    RelativeBaseOffset,
+    Halt
+}
+
+impl OpcodeType
+{
+    fn from_i64(value: i64) -> Option<Self>
+    {
+        match value
+        {
+            1 => Some(Self::Add),
+            2 => Some(Self::Mul),
+            3 => Some(Self::Input),
+            4 => Some(Self::Output),
+            5 => Some(Self::JumpIfTrue),
+            6 => Some(Self::JumpIfFalse),
+            7 => Some(Self::LessThan),
+            8 => Some(Self::Equals),
+            9 => Some(Self::RelativeBaseOffset),
+            99 => Some(Self::Halt),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ParameterMode
+{
+    Position,
+    Immediate,
+    Relative
+}
+
+impl ParameterMode
+{
+    fn from_digit(d: i64) -> Option<Self>
+    {
+        match d
+        {
+            0 => Some(Self::Position),
+            1 => Some(Self::Immediate),
+            2 => Some(Self::Relative),
+            _ => None
+        }
+    }
+
+    pub(crate) fn digit(&self) -> i64
+    {
+        match self
+        {
+            Self::Position => 0,
+            Self::Immediate => 1,
+            Self::Relative => 2
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Opcode
+{
+    Add(ParameterMode, ParameterMode, ParameterMode),
+    Mul(ParameterMode, ParameterMode, ParameterMode),
+    Input(ParameterMode),
+    Output(ParameterMode),
+    JumpIfTrue(ParameterMode, ParameterMode),
+    JumpIfFalse(ParameterMode, ParameterMode),
+    LessThan(ParameterMode, ParameterMode, ParameterMode),
+    Equals(ParameterMode, ParameterMode, ParameterMode),
+    RelativeBaseOffset(ParameterMode),
+    Halt
+}
+
+impl Opcode
+{
+    pub(crate) fn from_intcode(intcode: i64) -> Option<Self>
+    {
+        // opcodes are at most five digits: three parameter modes and a two digit type
+        if !(0..100_000).contains(&intcode)
+        {
+            return None;
+        }
+
+        let opcode_type = OpcodeType::from_i64(intcode % 100)?;
+
+        let param_1_type = ParameterMode::from_digit((intcode / 100) % 10)?;
+        let param_2_type = ParameterMode::from_digit((intcode / 1_000) % 10)?;
+        let param_3_type = ParameterMode::from_digit(intcode / 10_000)?;
+
+        match opcode_type
+        {
+            OpcodeType::Add => Some(Self::Add(param_1_type, param_2_type, param_3_type)),
+            OpcodeType::Mul => Some(Self::Mul(param_1_type, param_2_type, param_3_type)),
+            OpcodeType::Input => Some(Self::Input(param_1_type)),
+            OpcodeType::Output => Some(Self::Output(param_1_type)),
+            OpcodeType::JumpIfTrue => Some(Self::JumpIfTrue(param_1_type, param_2_type)),
+            OpcodeType::JumpIfFalse => Some(Self::JumpIfFalse(param_1_type, param_2_type)),
+            OpcodeType::LessThan => Some(Self::LessThan(param_1_type, param_2_type, param_3_type)),
+            OpcodeType::Equals => Some(Self::Equals(param_1_type, param_2_type, param_3_type)),
+            OpcodeType::RelativeBaseOffset => Some(Self::RelativeBaseOffset(param_1_type)),
+            OpcodeType::Halt => Some(Self::Halt)
+        }
+    }
+
+    // opcode with every parameter in position mode
+    pub(crate) fn from_mnemonic(mnemonic: &str) -> Option<Self>
+    {
+        (1..10).chain(Some(99))
+            .filter_map(Self::from_intcode)
+            .find(|o| o.mnemonic().eq_ignore_ascii_case(mnemonic))
+    }
+
+    pub(crate) fn to_intcode(self) -> i64
+    {
+        let opcode_type = match self
+        {
+            Self::Add(..) => 1,
+            Self::Mul(..) => 2,
+            Self::Input(..) => 3,
+            Self::Output(..) => 4,
+            Self::JumpIfTrue(..) => 5,
+            Self::JumpIfFalse(..) => 6,
+            Self::LessThan(..) => 7,
+            Self::Equals(..) => 8,
+            Self::RelativeBaseOffset(..) => 9,
+            Self::Halt => 99
+        };
+
+        return self.parameter_modes().iter()
+            .zip([100, 1_000, 10_000].iter())
+            .fold(opcode_type, |intcode, (mode, place)| intcode + mode.digit() * place);
+    }
+
+    pub(crate) fn length(&self) -> usize
+    {
+        match self
+        {
+            Self::Add(..) | Self::Mul(..) | Self::LessThan(..) | Self::Equals(..) => 4,
+            Self::JumpIfTrue(..) | Self::JumpIfFalse(..) => 3,
+            Self::Input(..) | Self::Output(..) | Self::RelativeBaseOffset(..) => 2,
+            Self::Halt => 1
+        }
+    }
+
+    pub(crate) fn mnemonic(&self) -> &'static str
+    {
+        match self
+        {
+            Self::Add(..) => "ADD",
+            Self::Mul(..) => "MUL",
+            Self::Input(..) => "IN",
+            Self::Output(..) => "OUT",
+            Self::JumpIfTrue(..) => "JNZ",
+            Self::JumpIfFalse(..) => "JZ",
+            Self::LessThan(..) => "LT",
+            Self::Equals(..) => "EQ",
+            Self::RelativeBaseOffset(..) => "ARB",
+            Self::Halt => "HALT"
+        }
+    }
+
+    pub(crate) fn parameter_modes(&self) -> Vec<ParameterMode>
+    {
+        match *self
+        {
+            Self::Add(a, b, c) | Self::Mul(a, b, c) | Self::LessThan(a, b, c) | Self::Equals(a, b, c) => vec![a, b, c],
+            Self::JumpIfTrue(a, b) | Self::JumpIfFalse(a, b) => vec![a, b],
+            Self::Input(a) | Self::Output(a) | Self::RelativeBaseOffset(a) => vec![a],
+            Self::Halt => vec![]
+        }
+    }
+
+    // whether the last parameter is the address the result gets written to
+    pub(crate) fn writes_last_parameter(&self) -> bool
+    {
+        matches!(self, Self::Add(..) | Self::Mul(..) | Self::Input(..) | Self::LessThan(..) | Self::Equals(..))
+    }
+
+    // Writing to an immediate parameter, which the assembler refuses. Computers
+    // without a strict ISA profile treat it as a position.
+    pub(crate) fn writes_immediate(&self) -> bool
+    {
+        return self.writes_last_parameter() && self.parameter_modes().last() == Some(&ParameterMode::Immediate);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::computer::{Computer, RunState};
+use crate::error::IntcodeError;
+use crate::word::Word;
+
+// A computer driven as an iterator, see Computer::into_outputs. Each call to
+// next runs the program up to its next output. The iterator ends when the
+// program halts or asks for more input than the input iterator has, and
+// after the first error.
+pub struct Outputs<'a, W = i64>
+{
+    computer: Computer<'a, W>,
+    // how the program stopped, once it has
+    stopped: Option<Result<RunState<W>, IntcodeError>>
+}
+
+impl<'a, W: Word> Outputs<'a, W>
+{
+    pub(crate) fn new(computer: Computer<'a, W>) -> Self
+    {
+        Self {computer, stopped: None}
+    }
+
+    // How the program stopped, None while it can still produce outputs. An
+    // error is the same one the iterator returned last.
+    pub fn state(&self) -> Option<Result<RunState<W>, &IntcodeError>>
+    {
+        return self.stopped.as_ref().map(|stopped| stopped.as_ref().cloned());
+    }
+
+    // the computer back, to look at its memory or keep running it
+    pub fn into_computer(self) -> Computer<'a, W>
+    {
+        return self.computer;
+    }
+}
+
+impl<'a, W: Word> Iterator for Outputs<'a, W>
+{
+    type Item = Result<W, IntcodeError>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if self.stopped.is_some()
+        {
+            return None;
+        }
+
+        match self.computer.run_until_output()
+        {
+            Ok(RunState::ProducedOutput(value)) => Some(Ok(value)),
+            Ok(state) => {
+                self.stopped = Some(Ok(state));
+                None
+            },
+            Err(e) => {
+                self.stopped = Some(Err(e.clone()));
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::network::{Network, NetworkError, Topology};
+use crate::program::Program;
+
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseSearch
+{
+    // best phase ordering and the signal it produced, None if no ordering produced a signal
+    pub best: Option<(Vec<i64>, i64)>,
+    // every ordering that was tried with the signal out of the last amplifier,
+    // None when the circuit deadlocked before producing one
+    pub signals: Vec<(Vec<i64>, Option<i64>)>
+}
+
+// every ordered selection of count values from values
+fn permutations(values: &[i64], count: usize) -> Vec<Vec<i64>>
+{
+    if count == 0
+    {
+        return vec![vec![]];
+    }
+
+    let mut result = Vec::new();
+    for (i, &first) in values.iter().enumerate()
+    {
+        let rest: Vec<i64> = values.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &v)| v).collect();
+
+        for mut tail in permutations(&rest, count - 1)
+        {
+            tail.insert(0, first);
+            result.push(tail);
+        }
+    }
+
+    return result;
+}
+
+// runs one circuit: every amplifier gets its phase, the first one also gets the 0 signal
+fn evaluate(program: &Program, phases: &[i64], topology: &Topology) -> Result<Option<i64>, NetworkError>
+{
+    let mut amplifiers = Network::from_program(program, phases.len(), topology.clone())?;
+
+    for (amplifier, &phase) in phases.iter().enumerate()
+    {
+        amplifiers.send(amplifier, phase);
+    }
+    amplifiers.send(0, 0);
+
+    amplifiers.run()?;
+
+    return Ok(amplifiers.last_output(phases.len() - 1));
+}
+
+// Tries every ordering of amplifiers phases out of the phase values, using
+// all CPU cores. Topology::Chain is the plain amplifier circuit, Topology::Ring
+// the feedback loop. The signal table is in lexicographic order of the phase
+// values as given, ties for the best signal go to the first ordering.
+pub fn search_phases(program: &Program, amplifiers: usize, phases: &[i64], topology: Topology) -> Result<PhaseSearch, NetworkError>
+{
+    if amplifiers == 0 || amplifiers > phases.len()
+    {
+        return Ok(PhaseSearch {best: None, signals: vec![]});
+    }
+
+    let orderings = permutations(phases, amplifiers);
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = orderings.len().div_ceil(workers);
+
+    let results: Vec<Result<Vec<Option<i64>>, NetworkError>> = thread::scope(|scope| {
+        let handles: Vec<_> = orderings.chunks(chunk_size).map(|chunk| {
+            let topology = &topology;
+            scope.spawn(move || chunk.iter().map(|ordering| evaluate(program, ordering, topology)).collect())
+        }).collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut signals = Vec::with_capacity(orderings.len());
+    let mut orderings = orderings.into_iter();
+    for result in results
+    {
+        for signal in result?
+        {
+            signals.push((orderings.next().unwrap(), signal));
+        }
+    }
+
+    let mut best: Option<(Vec<i64>, i64)> = None;
+    for (ordering, signal) in &signals
+    {
+        if let Some(signal) = *signal
+        {
+            if best.as_ref().is_none_or(|&(_, b)| signal > b)
+            {
+                best = Some((ordering.clone(), signal));
+            }
+        }
+    }
+
+    return Ok(PhaseSearch {best, signals});
+}
@@ -0,0 +1,238 @@
+use crate::disassembler::Instruction;
+use crate::word::Word;
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+// number of rows in each section of the report
+const REPORT_ROWS: usize = 10;
+
+#[derive(Debug, Clone, Copy)]
+struct AddressProfile
+{
+    executions: u64,
+    length: usize,
+    is_jump: bool,
+    halts: bool
+}
+
+// a loop closed by a backward jump, from the jump target to the jump
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HotLoop
+{
+    pub start: usize,
+    pub end: usize,
+    pub iterations: u64,
+    // instructions executed inside start..=end over the whole run
+    pub instructions_executed: u64
+}
+
+// straight-line code as it was executed: entered at start, left after the
+// instruction at end by a jump, a halt or falling into another block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BasicBlock
+{
+    pub start: usize,
+    pub end: usize,
+    pub instructions: usize,
+    pub executions: u64
+}
+
+// Counts what a Computer executes, see Computer::set_profiler. Printing it
+// gives a report of the hottest opcodes, loops, basic blocks and memory.
+#[derive(Debug, Clone, Default)]
+pub struct Profiler
+{
+    steps: u64,
+    first_address: Option<usize>,
+    addresses: HashMap<usize, AddressProfile>,
+    opcodes: HashMap<&'static str, u64>,
+    reads: HashMap<usize, u64>,
+    writes: HashMap<usize, u64>,
+    taken_jumps: HashMap<(usize, usize), u64>
+}
+
+fn ranked<K: Copy + Ord>(counts: &HashMap<K, u64>) -> Vec<(K, u64)>
+{
+    let mut ranked: Vec<(K, u64)> = counts.iter().map(|(&k, &count)| (k, count)).collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    return ranked;
+}
+
+impl Profiler
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub(crate) fn record<W: Word>(&mut self, instruction: &Instruction<W>, reads: &[usize], written: Option<usize>, next_address: usize)
+    {
+        self.steps += 1;
+        self.first_address.get_or_insert(instruction.address);
+
+        let profile = self.addresses.entry(instruction.address).or_insert(AddressProfile
+        {
+            executions: 0,
+            length: 0,
+            is_jump: false,
+            halts: false
+        });
+        profile.executions += 1;
+        profile.length = instruction.opcode.length();
+        profile.is_jump = instruction.is_jump();
+        profile.halts = !instruction.falls_through() && !instruction.is_jump();
+
+        *self.opcodes.entry(instruction.opcode.mnemonic()).or_insert(0) += 1;
+
+        for &address in reads
+        {
+            *self.reads.entry(address).or_insert(0) += 1;
+        }
+
+        if let Some(address) = written
+        {
+            *self.writes.entry(address).or_insert(0) += 1;
+        }
+
+        if instruction.is_jump() && next_address != instruction.next_address()
+        {
+            *self.taken_jumps.entry((instruction.address, next_address)).or_insert(0) += 1;
+        }
+    }
+
+    // instructions executed since profiling started
+    pub fn steps(&self) -> u64
+    {
+        return self.steps;
+    }
+
+    pub fn executions(&self, address: usize) -> u64
+    {
+        return self.addresses.get(&address).map_or(0, |p| p.executions);
+    }
+
+    pub fn reads(&self, address: usize) -> u64
+    {
+        return self.reads.get(&address).copied().unwrap_or(0);
+    }
+
+    pub fn writes(&self, address: usize) -> u64
+    {
+        return self.writes.get(&address).copied().unwrap_or(0);
+    }
+
+    // executions per opcode mnemonic, most executed first
+    pub fn opcodes(&self) -> Vec<(&'static str, u64)>
+    {
+        return ranked(&self.opcodes);
+    }
+
+    // instruction addresses, most executed first
+    pub fn hot_addresses(&self) -> Vec<(usize, u64)>
+    {
+        let executions: HashMap<usize, u64> = self.addresses.iter().map(|(&a, p)| (a, p.executions)).collect();
+        return ranked(&executions);
+    }
+
+    // loops ranked by the instructions executed inside them
+    pub fn hot_loops(&self) -> Vec<HotLoop>
+    {
+        let mut loops: Vec<HotLoop> = self.taken_jumps.iter()
+            .filter(|&(&(from, to), _)| to <= from)
+            .map(|(&(from, to), &iterations)| HotLoop
+            {
+                start: to,
+                end: from,
+                iterations,
+                instructions_executed: (to..=from).map(|a| self.executions(a)).sum()
+            })
+            .collect();
+
+        loops.sort_by(|a, b| b.instructions_executed.cmp(&a.instructions_executed).then(a.start.cmp(&b.start)));
+        return loops;
+    }
+
+    // basic blocks ranked by the instructions executed inside them
+    pub fn basic_blocks(&self) -> Vec<BasicBlock>
+    {
+        let mut leaders: BTreeSet<usize> = self.first_address.into_iter().collect();
+        leaders.extend(self.taken_jumps.keys().map(|&(_, to)| to));
+        leaders.extend(self.addresses.iter().filter(|(_, p)| p.is_jump).map(|(&a, p)| a + p.length));
+        leaders.retain(|a| self.addresses.contains_key(a));
+
+        let mut blocks: Vec<BasicBlock> = leaders.iter().map(|&start| {
+            let mut end = start;
+            let mut instructions = 1;
+
+            loop
+            {
+                let profile = self.addresses[&end];
+                let next = end + profile.length;
+
+                if profile.is_jump || profile.halts || leaders.contains(&next) || !self.addresses.contains_key(&next)
+                {
+                    break;
+                }
+
+                end = next;
+                instructions += 1;
+            }
+
+            BasicBlock {start, end, instructions, executions: self.executions(start)}
+        }).collect();
+
+        blocks.sort_by(|a, b| (b.instructions as u64 * b.executions).cmp(&(a.instructions as u64 * a.executions)).then(a.start.cmp(&b.start)));
+        return blocks;
+    }
+}
+
+impl fmt::Display for Profiler
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let percent = |count: u64| if self.steps == 0 {0.0} else {100.0 * count as f64 / self.steps as f64};
+
+        writeln!(f, "instructions executed: {}", self.steps)?;
+
+        writeln!(f, "\nopcodes:")?;
+        for (mnemonic, count) in self.opcodes()
+        {
+            writeln!(f, "  {:<5} {:>12} {:>6.2}%", mnemonic, count, percent(count))?;
+        }
+
+        writeln!(f, "\nhot loops:")?;
+        for l in self.hot_loops().iter().take(REPORT_ROWS)
+        {
+            writeln!(f, "  {:04}..{:04} {:>10} iterations {:>12} instructions {:>6.2}%",
+                     l.start, l.end, l.iterations, l.instructions_executed, percent(l.instructions_executed))?;
+        }
+
+        writeln!(f, "\nbasic blocks:")?;
+        for b in self.basic_blocks().iter().take(REPORT_ROWS)
+        {
+            writeln!(f, "  {:04}..{:04} {:>4} instructions {:>10} executions {:>6.2}%",
+                     b.start, b.end, b.instructions, b.executions, percent(b.instructions as u64 * b.executions))?;
+        }
+
+        writeln!(f, "\nhot addresses:")?;
+        for (address, count) in self.hot_addresses().into_iter().take(REPORT_ROWS)
+        {
+            writeln!(f, "  {:04} {:>12}", address, count)?;
+        }
+
+        writeln!(f, "\nmemory reads:")?;
+        for (address, count) in ranked(&self.reads).into_iter().take(REPORT_ROWS)
+        {
+            writeln!(f, "  {:04} {:>12}", address, count)?;
+        }
+
+        writeln!(f, "\nmemory writes:")?;
+        for (address, count) in ranked(&self.writes).into_iter().take(REPORT_ROWS)
+        {
+            writeln!(f, "  {:04} {:>12}", address, count)?;
+        }
+
+        return Ok(());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::word::Word;
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+// memory outside the dense region is allocated in pages of this many values
+pub(crate) const PAGE_SIZE: usize = 1024;
+
+// default ceiling on allocated memory, in values (512 MiB)
+pub const DEFAULT_MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+// addresses are i64 values in a program, nothing above this can be reached
+pub(crate) const MAX_ADDRESS: usize = i64::MAX as usize;
+
+// Memory is zero-filled and unbounded in principle. Addresses close to the end
+// of what's in use grow one dense Vec so normal programs stay fast, addresses
+// far beyond it get sparse pages of their own, so a single write to 10^9 only
+// costs one page. Growing past the memory limit fails instead of allocating.
+// Values are i64 unless a Computer works with a wider Word.
+#[derive(Debug, Clone)]
+pub struct Program<W = i64>
+{
+    pub(crate) memory: Vec<W>,
+    pages: BTreeMap<usize, Box<[W]>>,
+    len: usize,
+    limit: usize,
+    zero: W
+}
+
+impl<W: Word> Default for Program<W>
+{
+    fn default() -> Self
+    {
+        Self::from(Vec::new())
+    }
+}
+
+impl Program
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn new_from_str(initial_setup: String) -> Self
+    {
+        let mut intcode = Vec::<i64>::new();
+        for value in initial_setup.split(',')
+        {
+            intcode.push(value.parse::<i64>().unwrap());
+        }
+
+        Self::from(intcode)
+    }
+
+    // the same memory with wider values
+    pub(crate) fn widen<W: Word>(&self) -> Program<W>
+    {
+        let widen = |values: &[i64]| values.iter().map(|&v| W::from_i64(v)).collect::<Vec<W>>();
+
+        Program
+        {
+            memory: widen(&self.memory),
+            pages: self.pages.iter().map(|(&page, values)| (page, widen(values).into_boxed_slice())).collect(),
+            len: self.len,
+            limit: self.limit,
+            zero: W::from_i64(0)
+        }
+    }
+}
+
+impl<W: Word> Program<W>
+{
+    // one past the highest address that was loaded or written
+    #[inline]
+    pub fn len(&self) -> usize
+    {
+        return self.len;
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool
+    {
+        return self.len == 0;
+    }
+
+    pub fn memory_limit(&self) -> usize
+    {
+        return self.limit;
+    }
+
+    // The limit only stops memory from growing, a program that is already
+    // larger than the limit can still be loaded and run.
+    pub fn set_memory_limit(&mut self, limit: usize)
+    {
+        self.limit = limit;
+    }
+
+    // number of values actually allocated
+    pub fn allocated(&self) -> usize
+    {
+        return self.memory.len() + self.pages.len() * PAGE_SIZE;
+    }
+
+    #[inline]
+    pub fn get(&self, address: usize) -> W
+    {
+        return self[address].clone();
+    }
+
+    // None when the address is above MAX_ADDRESS, or isn't allocated yet and
+    // allocating it would go over the memory limit
+    #[inline]
+    pub fn get_mut(&mut self, address: usize) -> Option<&mut W>
+    {
+        if address < self.memory.len()
+        {
+            self.len = self.len.max(address + 1);
+            return Some(&mut self.memory[address]);
+        }
+
+        return self.get_mut_sparse(address);
+    }
+
+    // get_mut outside the dense region
+    #[inline(never)]
+    fn get_mut_sparse(&mut self, address: usize) -> Option<&mut W>
+    {
+        let end = match address.checked_add(1)
+        {
+            Some(end) if address <= MAX_ADDRESS => end,
+            _ => return None
+        };
+
+        if !self.pages.contains_key(&(address / PAGE_SIZE))
+        {
+            self.allocate(address)?;
+        }
+
+        self.len = self.len.max(end);
+
+        if address < self.memory.len()
+        {
+            return Some(&mut self.memory[address]);
+        }
+
+        return self.pages.get_mut(&(address / PAGE_SIZE)).map(|page| &mut page[address % PAGE_SIZE]);
+    }
+
+    fn allocate(&mut self, address: usize) -> Option<()>
+    {
+        let page = address / PAGE_SIZE;
+
+        if address < self.memory.len() + PAGE_SIZE
+        {
+            // near the end of the dense region, grow it up to the end of the page
+            let new_len = (page + 1) * PAGE_SIZE;
+
+            let absorbed: Vec<usize> = self.pages.range(..page + 1).map(|(&p, _)| p).collect();
+            let growth = new_len - self.memory.len() - absorbed.len() * PAGE_SIZE;
+            if self.allocated() + growth > self.limit
+            {
+                return None;
+            }
+
+            self.memory.resize(new_len, self.zero.clone());
+            for p in absorbed
+            {
+                let values = self.pages.remove(&p).unwrap();
+                self.memory[p * PAGE_SIZE..(p + 1) * PAGE_SIZE].clone_from_slice(&values);
+            }
+        }
+        else
+        {
+            if self.allocated() + PAGE_SIZE > self.limit
+            {
+                return None;
+            }
+
+            self.pages.insert(page, vec![self.zero.clone(); PAGE_SIZE].into_boxed_slice());
+        }
+
+        return Some(());
+    }
+
+    // Forgets about addresses from len onwards. The values there have to be
+    // zero already, this only undoes the growth of len().
+    pub(crate) fn shrink_to(&mut self, len: usize)
+    {
+        self.len = self.len.min(len);
+    }
+
+    // Contiguous runs of memory that may hold nonzero values, in address
+    // order, trimmed to len(). Sparse memory is only visited where it's allocated.
+    pub(crate) fn segments(&self) -> Vec<(usize, &[W])>
+    {
+        let mut segments = vec![(0, &self.memory[..self.memory.len().min(self.len)])];
+
+        for (&page, values) in &self.pages
+        {
+            let start = page * PAGE_SIZE;
+            if start < self.len
+            {
+                segments.push((start, &values[..PAGE_SIZE.min(self.len - start)]));
+            }
+        }
+
+        return segments;
+    }
+
+    fn nonzero(&self) -> impl Iterator<Item = (usize, &W)> + '_
+    {
+        self.segments().into_iter()
+            .flat_map(|(start, values)| values.iter().enumerate().map(move |(i, v)| (start + i, v)))
+            .filter(move |&(_, v)| *v != self.zero)
+    }
+}
+
+impl<W: Word> PartialEq for Program<W>
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        return self.len == other.len && self.nonzero().eq(other.nonzero());
+    }
+}
+
+impl<W: Word + Eq> Eq for Program<W> {}
+
+impl<W: Word> std::ops::Index<usize> for Program<W>
+{
+    type Output = W;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output
+    {
+        if index < self.memory.len()
+        {
+            return &self.memory[index];
+        }
+
+        match self.pages.get(&(index / PAGE_SIZE))
+        {
+            Some(page) => &page[index % PAGE_SIZE],
+            None => &self.zero
+        }
+    }
+}
+
+impl<W: Word> std::ops::IndexMut<usize> for Program<W>
+{
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output
+    {
+        let limit = self.limit;
+
+        match self.get_mut(index)
+        {
+            Some(value) => value,
+            None => panic!("Can't write to address {}, it's out of range or over the memory limit of {} values", index, limit)
+        }
+    }
+}
+
+impl<W: Word> From<Vec<W>> for Program<W>
+{
+    fn from(memory: Vec<W>) -> Self
+    {
+        Self
+        {
+            len: memory.len(),
+            memory,
+            pages: BTreeMap::new(),
+            limit: DEFAULT_MEMORY_LIMIT,
+            zero: W::from_i64(0)
+        }
+    }
+}
+
+impl<W: Word> fmt::Display for Program<W>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let values: Vec<String> = (0..self.len).map(|a| self.get(a).to_string()).collect();
+        write!(f, "{}", values.join(","))
+    }
+}
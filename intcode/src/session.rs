@@ -0,0 +1,300 @@
+use crate::computer::{Computer, RunState};
+use crate::error::IntcodeError;
+use crate::program::Program;
+use crate::snapshot::Snapshot;
+use crate::word::Word;
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+const FORMAT_NAME: &str = "intcode-session";
+const FORMAT_VERSION: u32 = 1;
+
+// A value that went in or out of the program. The step is the number of
+// instructions the computer had executed since recording started before the
+// IN or OUT instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent<W = i64>
+{
+    Input {step: u64, value: W},
+    Output {step: u64, value: W}
+}
+
+// Everything a Computer read and wrote while a session was recorded, see
+// Computer::set_session_recorder. Replaying it runs the program again on the
+// recorded inputs and checks it produces the same outputs at the same steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session<W = i64>
+{
+    events: Vec<SessionEvent<W>>
+}
+
+#[derive(Debug)]
+pub enum SessionError
+{
+    Io(io::Error),
+    UnsupportedVersion(String),
+    Malformed(String)
+}
+
+impl<W> SessionEvent<W>
+{
+    pub fn step(&self) -> u64
+    {
+        match self
+        {
+            Self::Input {step, ..} | Self::Output {step, ..} => *step
+        }
+    }
+}
+
+impl fmt::Display for SessionError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::UnsupportedVersion(v) => write!(f, "Unsupported session version {}", v),
+            Self::Malformed(line) => write!(f, "Malformed session line: {}", line)
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl From<io::Error> for SessionError
+{
+    fn from(e: io::Error) -> Self
+    {
+        Self::Io(e)
+    }
+}
+
+// Where a replay first went differently from the recording. Outputs are
+// numbered from 0 and given as (step, value).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError<W = i64>
+{
+    Intcode(IntcodeError),
+    OutputMismatch {index: usize, expected: (u64, W), actual: (u64, W)},
+    // more outputs than were recorded
+    UnexpectedOutput {index: usize, actual: (u64, W)},
+    // the program halted or ran out of recorded input before this output
+    MissingOutput {index: usize, expected: (u64, W), state: RunState<W>}
+}
+
+impl<W: Word> fmt::Display for ReplayError<W>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::Intcode(e) => write!(f, "{}", e),
+            Self::OutputMismatch {index, expected, actual} => {
+                write!(f, "Output {} was {} at step {}, recorded {} at step {}", index, actual.1, actual.0, expected.1, expected.0)
+            },
+            Self::UnexpectedOutput {index, actual} => {
+                write!(f, "Output {} ({} at step {}) wasn't recorded", index, actual.1, actual.0)
+            },
+            Self::MissingOutput {index, expected, state} => {
+                write!(f, "Output {} ({} at step {}) never came, the program stopped with {:?}", index, expected.1, expected.0, state)
+            }
+        }
+    }
+}
+
+impl<W: Word> std::error::Error for ReplayError<W> {}
+
+impl<W> From<IntcodeError> for ReplayError<W>
+{
+    fn from(e: IntcodeError) -> Self
+    {
+        Self::Intcode(e)
+    }
+}
+
+// The file format is plain text, one event per line with the step first:
+//
+//     intcode-session 1
+//     input 0 5
+//     output 12 7
+impl<W> Default for Session<W>
+{
+    fn default() -> Self
+    {
+        Self {events: Vec::new()}
+    }
+}
+
+impl Session
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+}
+
+impl<W: Word> Session<W>
+{
+    pub fn events(&self) -> &[SessionEvent<W>]
+    {
+        return &self.events;
+    }
+
+    pub fn inputs(&self) -> Vec<W>
+    {
+        self.events.iter().filter_map(|e| match e {SessionEvent::Input {value, ..} => Some(value.clone()), _ => None}).collect()
+    }
+
+    pub fn outputs(&self) -> Vec<W>
+    {
+        self.outputs_with_steps().into_iter().map(|(_, value)| value).collect()
+    }
+
+    fn outputs_with_steps(&self) -> Vec<(u64, W)>
+    {
+        self.events.iter().filter_map(|e| match e {SessionEvent::Output {step, value} => Some((*step, value.clone())), _ => None}).collect()
+    }
+
+    pub(crate) fn record(&mut self, event: SessionEvent<W>)
+    {
+        self.events.push(event);
+    }
+
+    // forgets the events of a step that was stepped back over
+    pub(crate) fn unrecord(&mut self, step: u64)
+    {
+        while self.events.last().is_some_and(|e| e.step() == step)
+        {
+            self.events.pop();
+        }
+    }
+
+    // Runs the program from the start on the recorded inputs and stops at the
+    // first output that differs from the recording, in value or in step.
+    pub fn replay(&self, program: &Program) -> Result<(), ReplayError<W>>
+    {
+        let mut computer = Computer::default();
+        computer.load_program(program);
+
+        return self.replay_on(computer);
+    }
+
+    // like replay, for a session that was recorded from the state in the snapshot on
+    pub fn replay_from(&self, snapshot: &Snapshot<W>) -> Result<(), ReplayError<W>>
+    {
+        let mut computer = Computer::default();
+        computer.restore(snapshot);
+
+        return self.replay_on(computer);
+    }
+
+    fn replay_on(&self, mut computer: Computer<W>) -> Result<(), ReplayError<W>>
+    {
+        let expected = self.outputs_with_steps();
+        let mut inputs = self.inputs().into_iter();
+
+        computer.set_input_source(move || inputs.next());
+        let start = computer.instructions_executed();
+
+        for index in 0..
+        {
+            let state = computer.run_until_output()?;
+
+            let actual = match state
+            {
+                RunState::ProducedOutput(value) => (computer.instructions_executed() - start - 1, value),
+                _ => return match expected.get(index)
+                {
+                    Some(expected) => Err(ReplayError::MissingOutput {index, expected: expected.clone(), state}),
+                    None => Ok(())
+                }
+            };
+
+            match expected.get(index)
+            {
+                Some(expected) if *expected != actual => return Err(ReplayError::OutputMismatch {index, expected: expected.clone(), actual}),
+                Some(_) => (),
+                None => return Err(ReplayError::UnexpectedOutput {index, actual})
+            }
+        }
+
+        unreachable!();
+    }
+
+    pub fn write_to<T: Write>(&self, mut writer: T) -> io::Result<()>
+    {
+        writeln!(writer, "{} {}", FORMAT_NAME, FORMAT_VERSION)?;
+
+        for event in &self.events
+        {
+            match event
+            {
+                SessionEvent::Input {step, value} => writeln!(writer, "input {} {}", step, value)?,
+                SessionEvent::Output {step, value} => writeln!(writer, "output {} {}", step, value)?
+            }
+        }
+
+        return writer.flush();
+    }
+
+    pub fn read_from<R: BufRead>(reader: R) -> Result<Self, SessionError>
+    {
+        let mut lines = reader.lines();
+
+        let header = match lines.next()
+        {
+            Some(line) => line?,
+            None => return Err(SessionError::Malformed(String::new()))
+        };
+
+        match header.split_once(' ')
+        {
+            Some((FORMAT_NAME, version)) if version.parse() == Ok(FORMAT_VERSION) => (),
+            Some((FORMAT_NAME, version)) => return Err(SessionError::UnsupportedVersion(version.to_string())),
+            _ => return Err(SessionError::Malformed(header))
+        }
+
+        let mut events = Vec::new();
+
+        for line in lines
+        {
+            let line = line?;
+            if line.is_empty()
+            {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split(' ').collect();
+            let malformed = || SessionError::Malformed(line.clone());
+
+            let (step, value) = match fields[..]
+            {
+                [_, step, value] => (step.parse().map_err(|_| malformed())?, value.parse().map_err(|_| malformed())?),
+                _ => return Err(malformed())
+            };
+
+            events.push(match fields[0]
+            {
+                "input" => SessionEvent::Input {step, value},
+                "output" => SessionEvent::Output {step, value},
+                _ => return Err(malformed())
+            });
+        }
+
+        return Ok(Self {events});
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()>
+    {
+        return self.write_to(BufWriter::new(File::create(path)?));
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SessionError>
+    {
+        return Self::read_from(BufReader::new(File::open(path)?));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::program::Program;
+use crate::word::Word;
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+const FORMAT_NAME: &str = "intcode-snapshot";
+const FORMAT_VERSION: u32 = 2;
+
+// Full state of a Computer. Pending input and output are only captured when
+// the computer is connected to IOQueues, closures and stdio can't be saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<W: Word = i64>
+{
+    pub(crate) memory: Program<W>,
+    pub(crate) instruction_pointer: usize,
+    pub(crate) relative_base_offset: usize,
+    pub(crate) pending_input: Option<Vec<W>>,
+    pub(crate) pending_output: Option<Vec<W>>
+}
+
+#[derive(Debug)]
+pub enum SnapshotError
+{
+    Io(io::Error),
+    UnsupportedVersion(String),
+    Malformed(String)
+}
+
+impl fmt::Display for SnapshotError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::UnsupportedVersion(v) => write!(f, "Unsupported snapshot version {}", v),
+            Self::Malformed(line) => write!(f, "Malformed snapshot line: {}", line)
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError
+{
+    fn from(e: io::Error) -> Self
+    {
+        Self::Io(e)
+    }
+}
+
+fn join<W: Word>(values: &[W]) -> String
+{
+    let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    return values.join(",");
+}
+
+fn split<W: Word>(line: &str, values: &str) -> Result<Vec<W>, SnapshotError>
+{
+    if values.is_empty()
+    {
+        return Ok(vec![]);
+    }
+
+    return values.split(',')
+        .map(|v| v.parse::<W>().map_err(|_| SnapshotError::Malformed(line.to_string())))
+        .collect();
+}
+
+// The file format is plain text, one field per line:
+//
+//     intcode-snapshot 2
+//     instruction_pointer 12
+//     relative_base_offset 1000
+//     memory 109,1,204,-1,...
+//     segment 1000000000 0,0,7,...
+//     input 5,6
+//     output
+//
+// Memory far beyond the rest is written as segments starting at the given
+// address. The input and output lines are left out when they couldn't be captured.
+// Values are written in full whatever the Word, a snapshot has to be loaded
+// with a Word at least as wide as the one it was taken with.
+impl<W: Word> Snapshot<W>
+{
+    pub fn instruction_pointer(&self) -> usize
+    {
+        return self.instruction_pointer;
+    }
+
+    pub fn relative_base_offset(&self) -> usize
+    {
+        return self.relative_base_offset;
+    }
+
+    pub fn write_to<T: Write>(&self, mut writer: T) -> io::Result<()>
+    {
+        writeln!(writer, "{} {}", FORMAT_NAME, FORMAT_VERSION)?;
+        writeln!(writer, "instruction_pointer {}", self.instruction_pointer)?;
+        writeln!(writer, "relative_base_offset {}", self.relative_base_offset)?;
+        let segments = self.memory.segments();
+        writeln!(writer, "memory {}", join(segments[0].1))?;
+
+        for (start, values) in &segments[1..]
+        {
+            writeln!(writer, "segment {} {}", start, join(values))?;
+        }
+
+        if let Some(input) = &self.pending_input
+        {
+            writeln!(writer, "input {}", join(input))?;
+        }
+
+        if let Some(output) = &self.pending_output
+        {
+            writeln!(writer, "output {}", join(output))?;
+        }
+
+        return writer.flush();
+    }
+
+    pub fn read_from<R: BufRead>(reader: R) -> Result<Self, SnapshotError>
+    {
+        let mut lines = reader.lines();
+
+        let header = match lines.next()
+        {
+            Some(line) => line?,
+            None => return Err(SnapshotError::Malformed(String::new()))
+        };
+
+        match header.split_once(' ')
+        {
+            Some((FORMAT_NAME, version)) if version.parse() == Ok(FORMAT_VERSION) => (),
+            Some((FORMAT_NAME, version)) => return Err(SnapshotError::UnsupportedVersion(version.to_string())),
+            _ => return Err(SnapshotError::Malformed(header))
+        }
+
+        let mut memory = None;
+        let mut instruction_pointer = None;
+        let mut relative_base_offset = None;
+        let mut pending_input = None;
+        let mut pending_output = None;
+        let mut segments = Vec::new();
+
+        for line in lines
+        {
+            let line = line?;
+            let (key, value) = line.split_once(' ').unwrap_or((&line, ""));
+            let malformed = || SnapshotError::Malformed(line.clone());
+
+            match key
+            {
+                "instruction_pointer" => instruction_pointer = Some(value.parse().map_err(|_| malformed())?),
+                "relative_base_offset" => relative_base_offset = Some(value.parse().map_err(|_| malformed())?),
+                "memory" => memory = Some(split(&line, value)?),
+                "segment" => {
+                    let (start, values) = value.split_once(' ').unwrap_or((value, ""));
+                    segments.push((start.parse::<usize>().map_err(|_| malformed())?, split(&line, values)?));
+                },
+                "input" => pending_input = Some(split(&line, value)?),
+                "output" => pending_output = Some(split(&line, value)?),
+                "" => (),
+                _ => return Err(malformed())
+            }
+        }
+
+        let missing = |field: &str| SnapshotError::Malformed(format!("missing {}", field));
+
+        let mut memory = Program::from(memory.ok_or_else(|| missing("memory"))?);
+        for (start, values) in segments
+        {
+            for (i, value) in values.into_iter().enumerate()
+            {
+                match start.checked_add(i).and_then(|address| memory.get_mut(address))
+                {
+                    Some(slot) => *slot = value,
+                    None => return Err(SnapshotError::Malformed(format!("segment {} is over the memory limit", start)))
+                }
+            }
+        }
+
+        return Ok(Self
+        {
+            memory,
+            instruction_pointer: instruction_pointer.ok_or_else(|| missing("instruction_pointer"))?,
+            relative_base_offset: relative_base_offset.ok_or_else(|| missing("relative_base_offset"))?,
+            pending_input,
+            pending_output
+        });
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()>
+    {
+        return self.write_to(BufWriter::new(File::create(path)?));
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SnapshotError>
+    {
+        return Self::read_from(BufReader::new(File::open(path)?));
+    }
+}
@@ -0,0 +1,530 @@
+use crate::computer::{Computer, RunState};
+use crate::limits::{LimitedRun, RunLimits};
+use crate::opcode::{Opcode, ParameterMode};
+use crate::program::Program;
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+// instructions a single run may take before it's taken to be stuck in a loop
+const DEFAULT_MAX_INSTRUCTIONS: u64 = 1_000_000;
+// input values tried before the search gives up, day-02 needs 10,000
+const DEFAULT_MAX_COMBINATIONS: u64 = 10_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveMethod
+{
+    // the target cell came out as a linear expression of the inputs
+    Linear,
+    // every combination of inputs was run
+    BruteForce
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution
+{
+    // one value per input, in the order the inputs were added
+    pub values: Vec<i64>,
+    pub method: SolveMethod
+}
+
+// constant + sum of coefficients[i] * input i
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Linear
+{
+    constant: i64,
+    coefficients: Vec<i64>
+}
+
+impl Linear
+{
+    fn constant(value: i64, inputs: usize) -> Self
+    {
+        Self {constant: value, coefficients: vec![0; inputs]}
+    }
+
+    fn input(index: usize, inputs: usize) -> Self
+    {
+        let mut coefficients = vec![0; inputs];
+        coefficients[index] = 1;
+        Self {constant: 0, coefficients}
+    }
+
+    fn as_constant(&self) -> Option<i64>
+    {
+        if self.coefficients.iter().all(|&c| c == 0) {Some(self.constant)} else {None}
+    }
+
+    fn add(&self, other: &Self) -> Option<Self>
+    {
+        let coefficients = self.coefficients.iter().zip(&other.coefficients)
+            .map(|(a, b)| a.checked_add(*b))
+            .collect::<Option<Vec<i64>>>()?;
+
+        Some(Self {constant: self.constant.checked_add(other.constant)?, coefficients})
+    }
+
+    fn scale(&self, factor: i64) -> Option<Self>
+    {
+        let coefficients = self.coefficients.iter()
+            .map(|a| a.checked_mul(factor))
+            .collect::<Option<Vec<i64>>>()?;
+
+        Some(Self {constant: self.constant.checked_mul(factor)?, coefficients})
+    }
+}
+
+// A cell during symbolic execution. Unknown is anything that isn't linear in
+// the inputs, which is fine as long as it never decides the control flow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value
+{
+    Linear(Linear),
+    Unknown
+}
+
+impl Value
+{
+    fn as_constant(&self) -> Option<i64>
+    {
+        match self
+        {
+            Self::Linear(l) => l.as_constant(),
+            Self::Unknown => None
+        }
+    }
+}
+
+// Runs the program once with the inputs left symbolic. None when that isn't
+// possible: an opcode, a jump, a write address or the relative base depends on
+// an input, the program wants input or it runs out of instructions.
+struct SymbolicRun<'p>
+{
+    program: &'p Program,
+    memory: BTreeMap<usize, Value>,
+    inputs: usize,
+    instruction_pointer: usize,
+    relative_base_offset: i64
+}
+
+impl<'p> SymbolicRun<'p>
+{
+    fn read(&self, address: usize) -> Value
+    {
+        match self.memory.get(&address)
+        {
+            Some(value) => value.clone(),
+            None => Value::Linear(Linear::constant(self.program.get(address), self.inputs))
+        }
+    }
+
+    fn concrete(&self, address: usize) -> Option<i64>
+    {
+        self.read(address).as_constant()
+    }
+
+    fn address(&self, mode: ParameterMode, offset: usize) -> Option<usize>
+    {
+        let parameter = self.concrete(self.instruction_pointer + offset)?;
+
+        let address = match mode
+        {
+            ParameterMode::Relative => parameter.checked_add(self.relative_base_offset)?,
+            _ => parameter
+        };
+
+        if address < 0 {None} else {Some(address as usize)}
+    }
+
+    fn parameter(&self, mode: ParameterMode, offset: usize) -> Value
+    {
+        match mode
+        {
+            ParameterMode::Immediate => self.read(self.instruction_pointer + offset),
+            // reading through an address that depends on an input could read anything
+            _ => self.address(mode, offset).map_or(Value::Unknown, |a| self.read(a))
+        }
+    }
+
+    fn write(&mut self, mode: ParameterMode, offset: usize, value: Value) -> Option<()>
+    {
+        let address = self.address(mode, offset)?;
+        self.memory.insert(address, value);
+        Some(())
+    }
+
+    fn run(&mut self, max_instructions: u64) -> Option<()>
+    {
+        for _ in 0..max_instructions
+        {
+            let opcode = Opcode::from_intcode(self.concrete(self.instruction_pointer)?)?;
+
+            match opcode
+            {
+                Opcode::Add(a, b, c) | Opcode::Mul(a, b, c) => {
+                    let result = match (self.parameter(a, 1), self.parameter(b, 2))
+                    {
+                        (Value::Linear(x), Value::Linear(y)) => {
+                            let result = match opcode
+                            {
+                                Opcode::Add(..) => x.add(&y),
+                                _ => match (x.as_constant(), y.as_constant())
+                                {
+                                    (Some(k), _) => y.scale(k),
+                                    (_, Some(k)) => x.scale(k),
+                                    _ => None
+                                }
+                            };
+                            result.map_or(Value::Unknown, Value::Linear)
+                        },
+                        _ => Value::Unknown
+                    };
+                    self.write(c, 3, result)?;
+                },
+                Opcode::LessThan(a, b, c) | Opcode::Equals(a, b, c) => {
+                    let result = match (self.parameter(a, 1).as_constant(), self.parameter(b, 2).as_constant())
+                    {
+                        (Some(x), Some(y)) => {
+                            let holds = if matches!(opcode, Opcode::LessThan(..)) {x < y} else {x == y};
+                            Value::Linear(Linear::constant(holds as i64, self.inputs))
+                        },
+                        _ => Value::Unknown
+                    };
+                    self.write(c, 3, result)?;
+                },
+                Opcode::JumpIfTrue(z, d) | Opcode::JumpIfFalse(z, d) => {
+                    let jump_if = matches!(opcode, Opcode::JumpIfTrue(..));
+                    if (self.parameter(z, 1).as_constant()? != 0) == jump_if
+                    {
+                        let target = self.parameter(d, 2).as_constant()?;
+                        if target < 0
+                        {
+                            return None;
+                        }
+
+                        self.instruction_pointer = target as usize;
+                        continue;
+                    }
+                },
+                Opcode::RelativeBaseOffset(a) => {
+                    self.relative_base_offset = self.relative_base_offset.checked_add(self.parameter(a, 1).as_constant()?)?;
+                },
+                Opcode::Output(_) => (),
+                Opcode::Input(_) => return None,
+                Opcode::Halt => return Some(())
+            }
+
+            self.instruction_pointer += opcode.length();
+        }
+
+        return None;
+    }
+}
+
+// smallest and largest a term can be over the input's range
+fn term_bounds(coefficient: i64, range: &RangeInclusive<i64>) -> (i128, i128)
+{
+    let (a, b) = (coefficient as i128 * *range.start() as i128, coefficient as i128 * *range.end() as i128);
+    return (a.min(b), a.max(b));
+}
+
+// Searches the inputs in order, the last one with a nonzero coefficient is
+// solved for directly. Branches that can't reach the target are cut off, and
+// the search gives up once it has tried as many values as the budget allows.
+struct LinearSearch<'e>
+{
+    expression: &'e Linear,
+    ranges: &'e [RangeInclusive<i64>],
+    target: i128,
+    values: Vec<i64>,
+    budget: u64
+}
+
+impl<'e> LinearSearch<'e>
+{
+    fn search(&mut self, searched: &[usize], last: usize, sum: i128) -> bool
+    {
+        let (low, high) = searched.iter().chain(Some(&last))
+            .map(|&i| term_bounds(self.expression.coefficients[i], &self.ranges[i]))
+            .fold((sum, sum), |(l, h), (a, b)| (l + a, h + b));
+
+        if self.target < low || self.target > high
+        {
+            return false;
+        }
+
+        if let Some((&input, rest)) = searched.split_first()
+        {
+            for value in self.feasible(input, rest, last, sum).into_iter().flatten()
+            {
+                if self.budget == 0
+                {
+                    return false;
+                }
+                self.budget -= 1;
+
+                self.values[input] = value;
+                let sum = sum + self.expression.coefficients[input] as i128 * value as i128;
+
+                if self.search(rest, last, sum)
+                {
+                    return true;
+                }
+            }
+
+            return false;
+        }
+
+        let coefficient = self.expression.coefficients[last] as i128;
+        if (self.target - sum) % coefficient != 0
+        {
+            return false;
+        }
+
+        let value = (self.target - sum) / coefficient;
+        if value < *self.ranges[last].start() as i128 || value > *self.ranges[last].end() as i128
+        {
+            return false;
+        }
+
+        self.values[last] = value as i64;
+        return true;
+    }
+
+    // the values of input that leave the target in reach of the inputs after it
+    fn feasible(&self, input: usize, rest: &[usize], last: usize, sum: i128) -> Option<RangeInclusive<i64>>
+    {
+        let (low, high) = rest.iter().chain(Some(&last))
+            .map(|&i| term_bounds(self.expression.coefficients[i], &self.ranges[i]))
+            .fold((0, 0), |(l, h), (a, b)| (l + a, h + b));
+
+        // coefficient * value has to land in [target - sum - high, target - sum - low]
+        let coefficient = self.expression.coefficients[input] as i128;
+        let (mut from, mut to) = (self.target - sum - high, self.target - sum - low);
+        if coefficient < 0
+        {
+            (from, to) = (-to, -from);
+        }
+        let coefficient = coefficient.abs();
+
+        let start = (from.div_euclid(coefficient) + (from.rem_euclid(coefficient) != 0) as i128)
+            .max(*self.ranges[input].start() as i128);
+        let end = to.div_euclid(coefficient).min(*self.ranges[input].end() as i128);
+
+        // start and end are inside the input's range unless it's left empty
+        if start > end
+        {
+            return None;
+        }
+
+        return Some(start as i64..=end as i64);
+    }
+}
+
+// the lexicographically smallest input values that make the expression equal the target
+fn solve_linear(expression: &Linear, ranges: &[RangeInclusive<i64>], target: i64, budget: u64) -> Option<Vec<i64>>
+{
+    // inputs that don't matter keep their smallest value
+    let values: Vec<i64> = ranges.iter().map(|r| *r.start()).collect();
+    let relevant: Vec<usize> = (0..ranges.len()).filter(|&i| expression.coefficients[i] != 0).collect();
+
+    let (&last, searched) = match relevant.split_last()
+    {
+        Some(split) => split,
+        None => return if expression.constant == target {Some(values)} else {None}
+    };
+
+    let mut search = LinearSearch {expression, ranges, target: target as i128, values, budget};
+
+    if search.search(searched, last, expression.constant as i128)
+    {
+        return Some(search.values);
+    }
+
+    return None;
+}
+
+// Finds values for chosen memory cells that leave a target value at an output
+// address once the program halts, the day-02 noun and verb search in general.
+// The program is first run once with the inputs as symbols, when the output
+// comes out linear in them it's solved directly. Otherwise, or when that finds
+// nothing, every combination is run, spread over all CPU cores. Either way the answer is the first
+// combination in lexicographic order of the inputs as they were added. Neither
+// search tries more combinations than with_max_combinations allows, past that
+// solve gives up and returns None.
+pub struct Solver
+{
+    program: Program,
+    output_address: usize,
+    inputs: Vec<(usize, RangeInclusive<i64>)>,
+    max_instructions: u64,
+    max_combinations: u64
+}
+
+impl Solver
+{
+    pub fn new(program: &Program, output_address: usize) -> Self
+    {
+        Self
+        {
+            program: program.clone(),
+            output_address,
+            inputs: Vec::new(),
+            max_instructions: DEFAULT_MAX_INSTRUCTIONS,
+            max_combinations: DEFAULT_MAX_COMBINATIONS
+        }
+    }
+
+    // memory cell to solve for and the values it may take
+    pub fn with_input(mut self, address: usize, values: RangeInclusive<i64>) -> Self
+    {
+        self.inputs.push((address, values));
+        self
+    }
+
+    // runs taking longer than this are treated as not producing the target
+    pub fn with_max_instructions(mut self, max_instructions: u64) -> Self
+    {
+        self.max_instructions = max_instructions;
+        self
+    }
+
+    // most input combinations either search may try
+    pub fn with_max_combinations(mut self, max_combinations: u64) -> Self
+    {
+        self.max_combinations = max_combinations;
+        self
+    }
+
+    pub fn solve(&self, target: i64) -> Option<Solution>
+    {
+        if self.inputs.iter().any(|(_, range)| range.is_empty())
+        {
+            return None;
+        }
+
+        if let Some(expression) = self.linear_expression()
+        {
+            let ranges: Vec<RangeInclusive<i64>> = self.inputs.iter().map(|(_, r)| r.clone()).collect();
+
+            // the symbolic run can't see everything a real one can, so the answer
+            // is checked and anything it got wrong is left to the brute force
+            if let Some(values) = solve_linear(&expression, &ranges, target, self.max_combinations).filter(|values| self.produces(values, target))
+            {
+                return Some(Solution {values, method: SolveMethod::Linear});
+            }
+        }
+
+        return self.brute_force(target).map(|values| Solution {values, method: SolveMethod::BruteForce});
+    }
+
+    fn linear_expression(&self) -> Option<Linear>
+    {
+        let inputs = self.inputs.len();
+        let mut run = SymbolicRun
+        {
+            program: &self.program,
+            memory: BTreeMap::new(),
+            inputs,
+            instruction_pointer: 0,
+            relative_base_offset: 0
+        };
+
+        for (index, (address, _)) in self.inputs.iter().enumerate()
+        {
+            run.memory.insert(*address, Value::Linear(Linear::input(index, inputs)));
+        }
+
+        run.run(self.max_instructions)?;
+
+        match run.read(self.output_address)
+        {
+            Value::Linear(expression) => Some(expression),
+            Value::Unknown => None
+        }
+    }
+
+    fn produces(&self, values: &[i64], target: i64) -> bool
+    {
+        let mut computer = Computer::new();
+        computer.load_program(&self.program);
+
+        for ((address, _), &value) in self.inputs.iter().zip(values)
+        {
+            if computer.try_set_value(*address, value).is_err()
+            {
+                return false;
+            }
+        }
+
+        let limits = RunLimits::new().with_max_instructions(self.max_instructions);
+
+        return match computer.run_with_limits(&limits)
+        {
+            Ok(LimitedRun::Finished(RunState::Halted)) => computer.get_value(self.output_address) == target,
+            _ => false
+        };
+    }
+
+    // Input values for the combination with the given index, the last input
+    // changes fastest. sizes are the number of values each input can take.
+    fn combination(&self, sizes: &[u64], mut index: u64) -> Vec<i64>
+    {
+        let mut values = vec![0; self.inputs.len()];
+
+        for (i, (_, range)) in self.inputs.iter().enumerate().rev()
+        {
+            values[i] = (*range.start() as i128 + (index % sizes[i]) as i128) as i64;
+            index /= sizes[i];
+        }
+
+        return values;
+    }
+
+    // gives up when there are more combinations than the limit
+    fn brute_force(&self, target: i64) -> Option<Vec<i64>>
+    {
+        let sizes: Vec<u64> = self.inputs.iter()
+            .map(|(_, r)| u64::try_from(*r.end() as i128 - *r.start() as i128 + 1).ok())
+            .collect::<Option<_>>()?;
+        let combinations = sizes.iter().try_fold(1u64, |total, &size| total.checked_mul(size))
+            .filter(|&total| total <= self.max_combinations)?;
+
+        let workers = thread::available_parallelism().map_or(1, |n| n.get()) as u64;
+        let chunk_size = combinations.div_ceil(workers).max(1);
+
+        // lowest index found so far, workers give up on everything after it
+        let found = AtomicU64::new(u64::MAX);
+
+        thread::scope(|scope| {
+            for start in (0..combinations).step_by(chunk_size as usize)
+            {
+                let (found, sizes) = (&found, &sizes);
+                scope.spawn(move || {
+                    for index in start..(start + chunk_size).min(combinations)
+                    {
+                        if index > found.load(Ordering::Relaxed)
+                        {
+                            return;
+                        }
+
+                        if self.produces(&self.combination(sizes, index), target)
+                        {
+                            found.fetch_min(index, Ordering::Relaxed);
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        return match found.into_inner()
+        {
+            u64::MAX => None,
+            index => Some(self.combination(&sizes, index))
+        };
+    }
+}
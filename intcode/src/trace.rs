@@ -0,0 +1,136 @@
+use crate::computer::MemoryWrite;
+use crate::opcode::{Opcode, ParameterMode};
+use crate::word::Word;
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+// what an instruction looked like right before it was executed
+pub(crate) struct TraceRecord<W>
+{
+    pub(crate) instruction_pointer: usize,
+    pub(crate) opcode: Opcode,
+    pub(crate) parameters: Vec<W>,
+    pub(crate) operands: Vec<W>,
+    pub(crate) relative_base_offset: usize
+}
+
+// values of any width are written as JSON numbers, it's up to the reader to keep them exact
+fn json_array<W: Word>(values: &[W]) -> String
+{
+    let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    return format!("[{}]", values.join(","));
+}
+
+// Tracer writes one JSON object per executed instruction, e.g.
+//
+// {"step":7,"ip":12,"opcode":"ADD","modes":["relative","immediate","position"],
+//  "parameters":[3,5,100],"operands":[7,5,100],"write":{"address":100,"old":0,"new":12},
+//  "relative_base":1000}
+//
+// Operands are the values read for each parameter, or the resolved address
+// for the parameter an instruction writes to. "write" is null when the
+// instruction didn't write to memory.
+pub struct Tracer<'a>
+{
+    writer: Box<dyn Write + 'a>,
+    address_range: Option<Range<usize>>,
+    step: u64,
+    error: Option<io::Error>
+}
+
+impl<'a> Tracer<'a>
+{
+    pub fn new<W: Write + 'a>(writer: W) -> Self
+    {
+        Self
+        {
+            writer: Box::new(BufWriter::new(writer)),
+            address_range: None,
+            step: 0,
+            error: None
+        }
+    }
+
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self>
+    {
+        return Ok(Self::new(File::create(path)?));
+    }
+
+    // only record instructions whose address falls in the range
+    pub fn with_address_range(mut self, address_range: Range<usize>) -> Self
+    {
+        self.address_range = Some(address_range);
+        return self;
+    }
+
+    // flushes the trace, reporting the first write error if there was one
+    pub fn finish(mut self) -> io::Result<()>
+    {
+        if let Some(e) = self.error.take()
+        {
+            return Err(e);
+        }
+
+        return self.writer.flush();
+    }
+
+    pub(crate) fn wants(&self, address: usize) -> bool
+    {
+        match &self.address_range
+        {
+            Some(range) => range.contains(&address),
+            None => true
+        }
+    }
+
+    // counts an instruction that was filtered out so step numbers stay comparable
+    pub(crate) fn skip(&mut self)
+    {
+        self.step += 1;
+    }
+
+    pub(crate) fn record<W: Word>(&mut self, record: TraceRecord<W>, write: Option<MemoryWrite<W>>)
+    {
+        self.step += 1;
+
+        // errors are kept for finish, a broken trace file shouldn't stop the program
+        if self.error.is_some()
+        {
+            return;
+        }
+
+        let modes: Vec<String> = record.opcode.parameter_modes().iter().map(|m| {
+            match m
+            {
+                ParameterMode::Position => "\"position\"",
+                ParameterMode::Immediate => "\"immediate\"",
+                ParameterMode::Relative => "\"relative\""
+            }.to_string()
+        }).collect();
+
+        let write = match write
+        {
+            Some(w) => format!("{{\"address\":{},\"old\":{},\"new\":{}}}", w.address, w.old_value, w.new_value),
+            None => "null".to_string()
+        };
+
+        let result = writeln!(self.writer,
+                              "{{\"step\":{},\"ip\":{},\"opcode\":\"{}\",\"modes\":[{}],\"parameters\":{},\"operands\":{},\"write\":{},\"relative_base\":{}}}",
+                              self.step,
+                              record.instruction_pointer,
+                              record.opcode.mnemonic(),
+                              modes.join(","),
+                              json_array(&record.parameters),
+                              json_array(&record.operands),
+                              write,
+                              record.relative_base_offset);
+
+        if let Err(e) = result
+        {
+            self.error = Some(e);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+// A value a Computer computes with, i64 unless it's given a wider one:
+// Computer<i128>, or Computer<BigInt> with the bigint feature, which never
+// overflows. Addresses, opcodes and relative base adjustments still have to
+// fit in an i64, only the data can be wider. Values are read and written as
+// decimal text wherever they leave the computer.
+pub trait Word: 'static + Clone + PartialEq + PartialOrd + fmt::Debug + fmt::Display + FromStr
+{
+    fn from_i64(value: i64) -> Self;
+    fn to_i64(&self) -> Option<i64>;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    fn wrapping_add(&self, other: &Self) -> Self;
+    fn wrapping_mul(&self, other: &Self) -> Self;
+}
+
+impl Word for i64
+{
+    #[inline]
+    fn from_i64(value: i64) -> Self
+    {
+        value
+    }
+
+    #[inline]
+    fn to_i64(&self) -> Option<i64>
+    {
+        Some(*self)
+    }
+
+    #[inline]
+    fn checked_add(&self, other: &Self) -> Option<Self>
+    {
+        i64::checked_add(*self, *other)
+    }
+
+    #[inline]
+    fn checked_mul(&self, other: &Self) -> Option<Self>
+    {
+        i64::checked_mul(*self, *other)
+    }
+
+    #[inline]
+    fn wrapping_add(&self, other: &Self) -> Self
+    {
+        i64::wrapping_add(*self, *other)
+    }
+
+    #[inline]
+    fn wrapping_mul(&self, other: &Self) -> Self
+    {
+        i64::wrapping_mul(*self, *other)
+    }
+}
+
+impl Word for i128
+{
+    #[inline]
+    fn from_i64(value: i64) -> Self
+    {
+        value as i128
+    }
+
+    #[inline]
+    fn to_i64(&self) -> Option<i64>
+    {
+        i64::try_from(*self).ok()
+    }
+
+    #[inline]
+    fn checked_add(&self, other: &Self) -> Option<Self>
+    {
+        i128::checked_add(*self, *other)
+    }
+
+    #[inline]
+    fn checked_mul(&self, other: &Self) -> Option<Self>
+    {
+        i128::checked_mul(*self, *other)
+    }
+
+    #[inline]
+    fn wrapping_add(&self, other: &Self) -> Self
+    {
+        i128::wrapping_add(*self, *other)
+    }
+
+    #[inline]
+    fn wrapping_mul(&self, other: &Self) -> Self
+    {
+        i128::wrapping_mul(*self, *other)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Word for num_bigint::BigInt
+{
+    fn from_i64(value: i64) -> Self
+    {
+        Self::from(value)
+    }
+
+    fn to_i64(&self) -> Option<i64>
+    {
+        num_traits::ToPrimitive::to_i64(self)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self>
+    {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self>
+    {
+        Some(self * other)
+    }
+
+    fn wrapping_add(&self, other: &Self) -> Self
+    {
+        // never overflows
+        self + other
+    }
+
+    fn wrapping_mul(&self, other: &Self) -> Self
+    {
+        self * other
+    }
+}
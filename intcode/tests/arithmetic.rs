@@ -0,0 +1,63 @@
+use intcode::{Arithmetic, Computer, IntcodeErrorKind, Program};
+
+// MUL x, x -> x; OUT x; HALT with x = 2^40
+const SQUARE: &str = "2,7,7,7,4,7,99,1099511627776";
+
+// MUL x, x -> x twice; OUT x; HALT with x = 2^40
+const SQUARE_TWICE: &str = "2,11,11,11,2,11,11,11,4,11,99,1099511627776";
+
+fn square_with(arithmetic: Arithmetic) -> Result<Vec<i64>, IntcodeErrorKind>
+{
+    let mut computer = Computer::new();
+    computer.set_arithmetic(arithmetic);
+    computer.load_program(&Program::new_from_str(SQUARE.to_string()));
+    computer.into_outputs(vec![]).map(|output| output.map_err(|error| error.kind)).collect()
+}
+
+#[test]
+fn overflow_stops_the_program_by_default()
+{
+    assert_eq!(Arithmetic::default(), Arithmetic::Checked);
+    assert_eq!(square_with(Arithmetic::Checked), Err(IntcodeErrorKind::Overflow));
+}
+
+#[test]
+fn wrapping_arithmetic_wraps()
+{
+    assert_eq!(square_with(Arithmetic::Wrapping), Ok(vec![0]));
+}
+
+#[test]
+fn wide_computer_keeps_the_result()
+{
+    let mut computer = Computer::<i128>::default();
+    computer.load_program(&Program::new_from_str(SQUARE.to_string()));
+
+    let outputs: Vec<i128> = computer.into_outputs(vec![]).map(Result::unwrap).collect();
+    assert_eq!(outputs, vec![1 << 80]);
+}
+
+#[test]
+fn wide_computer_checks_its_own_overflow()
+{
+    // squaring 2^80 no longer fits in 128 bits
+    let mut computer = Computer::<i128>::default();
+    computer.load_program(&Program::new_from_str(SQUARE_TWICE.to_string()));
+
+    let error = computer.run().unwrap_err();
+    assert_eq!(error.kind, IntcodeErrorKind::Overflow);
+    assert_eq!(error.instruction_pointer, 4);
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn bigint_computer_never_overflows()
+{
+    use num_bigint::BigInt;
+
+    let mut computer = Computer::<BigInt>::default();
+    computer.load_program(&Program::new_from_str(SQUARE_TWICE.to_string()));
+
+    let outputs: Vec<BigInt> = computer.into_outputs(vec![]).map(Result::unwrap).collect();
+    assert_eq!(outputs, vec![BigInt::from(1) << 160]);
+}
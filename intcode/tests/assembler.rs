@@ -0,0 +1,56 @@
+use intcode::{assemble, AssemblerError, AssemblerErrorKind, Computer, Program};
+
+// quad calls double twice, so the second CALL runs with a return address already on the stack
+const NESTED_CALLS: &str = "
+        ARB #stack
+        IN -> [arg]
+        CALL #quad
+        OUT [arg]
+        HALT
+quad:   CALL #double
+        CALL #double
+        RET
+double: MUL [arg], #2 -> [arg]
+        RET
+arg:    .data 0
+stack:  .data 0
+";
+
+#[test]
+fn labels_and_data_assemble_to_intcode()
+{
+    let source = "
+start:  IN -> [value]       ; read into the data below
+        ADD [value], #1 -> [value]
+        OUT [value]
+        HALT
+value:  .data 0
+";
+
+    assert_eq!(assemble(source), Ok(Program::new_from_str("3,9,1001,9,1,9,4,9,99,0".to_string())));
+}
+
+#[test]
+fn nested_calls_return_to_their_callers()
+{
+    let program = assemble(NESTED_CALLS).unwrap();
+    let mut computer = Computer::new();
+    computer.load_program(&program);
+
+    let outputs: Vec<i64> = computer.into_outputs(vec![3]).map(Result::unwrap).collect();
+    assert_eq!(outputs, vec![12]);
+}
+
+#[test]
+fn undefined_label_is_an_error()
+{
+    assert_eq!(assemble("IN -> [value]\nHALT\n"),
+               Err(AssemblerError {kind: AssemblerErrorKind::UndefinedLabel("value".to_string()), line: 1}));
+}
+
+#[test]
+fn immediate_destination_is_an_error()
+{
+    assert_eq!(assemble("HALT\nADD #1, #2 -> #3\n"),
+               Err(AssemblerError {kind: AssemblerErrorKind::ImmediateDestination, line: 2}));
+}
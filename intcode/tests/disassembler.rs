@@ -0,0 +1,54 @@
+use intcode::{assemble, disassemble, Program};
+
+// IN x; ADD x, 1 -> x; OUT x; HALT
+const ADD_ONE: &str = "3,9,1001,9,1,9,4,9,99,0";
+
+// JMP over a 99 that is never run; ADD #1, #2 -> [rb+0]; HALT
+const JUMP_OVER_DATA: &str = "1105,1,4,99,21101,1,2,0,99";
+
+#[test]
+fn listing_shows_mnemonics_and_data()
+{
+    let program = Program::new_from_str(ADD_ONE.to_string());
+
+    assert_eq!(disassemble(&program), "\
+0000: IN -> [9]
+0002: ADD [9], #1 -> [9]
+0006: OUT [9]
+0008: HALT
+0009: .data 0
+");
+}
+
+#[test]
+fn unreached_values_are_listed_as_data()
+{
+    let program = Program::new_from_str(JUMP_OVER_DATA.to_string());
+
+    assert_eq!(disassemble(&program), "\
+0000: JNZ #1, #4
+0003: .data 99
+0004: ADD #1, #2 -> [rb+0]
+0008: HALT
+");
+}
+
+#[test]
+fn listing_assembles_back_to_the_same_program()
+{
+    for intcode in [ADD_ONE, JUMP_OVER_DATA, "109,19,204,-34,99", "1002,4,3,4,33"]
+    {
+        let program = Program::new_from_str(intcode.to_string());
+        assert_eq!(assemble(&disassemble(&program)), Ok(program), "{}", intcode);
+    }
+}
+
+#[test]
+fn immediate_write_is_kept_as_data()
+{
+    let program = Program::new_from_str("11101,1,2,3,99".to_string());
+    let listing = disassemble(&program);
+
+    assert!(listing.starts_with("0000: .data 11101, 1, 2, 3 ; ADD #1, #2 -> #3\n"), "{}", listing);
+    assert_eq!(assemble(&listing), Ok(program));
+}
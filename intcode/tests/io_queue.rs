@@ -0,0 +1,63 @@
+use intcode::{Computer, IOQueue, IOQueueError, Program, RunState};
+
+// OUT #1; OUT #2; OUT #3; HALT
+const COUNT_TO_THREE: &str = "104,1,104,2,104,3,99";
+
+#[test]
+fn bounded_queue_refuses_values_once_full()
+{
+    let queue: IOQueue = IOQueue::bounded(1);
+    assert_eq!(queue.capacity(), Some(1));
+
+    assert_eq!(queue.push_back(1), Ok(()));
+    assert!(queue.is_full());
+    assert_eq!(queue.push_back(2), Err(IOQueueError::Full(2)));
+    assert_eq!(queue.push_front(3), Err(IOQueueError::Full(3)));
+    assert_eq!(queue.values(), vec![1]);
+
+    assert_eq!(queue.pop_front(), Some(1));
+    assert!(!queue.is_full());
+    assert_eq!(queue.push_back(2), Ok(()));
+}
+
+#[test]
+fn unbounded_queue_is_never_full()
+{
+    let queue = IOQueue::new();
+    assert_eq!(queue.capacity(), None);
+
+    for value in 0..10_000
+    {
+        queue.push_back(value).unwrap();
+    }
+    assert!(!queue.is_full());
+    assert_eq!(queue.len(), 10_000);
+}
+
+#[test]
+fn computer_waits_for_room_in_a_full_output_queue()
+{
+    let output: IOQueue = IOQueue::bounded(2);
+    let mut computer = Computer::new();
+    computer.load_program(&Program::new_from_str(COUNT_TO_THREE.to_string()));
+    computer.set_output_queue(&output);
+
+    // stops before the third OUT without losing its value
+    assert_eq!(computer.run(), Ok(RunState::OutputBlocked));
+    assert_eq!(computer.instruction_pointer(), 4);
+    assert_eq!(output.values(), vec![1, 2]);
+
+    assert_eq!(output.pop_front(), Some(1));
+    assert_eq!(computer.run(), Ok(RunState::Halted));
+    assert_eq!(output.values(), vec![2, 3]);
+}
+
+#[test]
+fn typed_numbers_are_parsed()
+{
+    let queue = IOQueue::new();
+
+    assert_eq!(queue.push_back_str(" -42\n"), Ok(()));
+    assert_eq!(queue.push_back_str("forty-two"), Err(IOQueueError::NotANumber("forty-two".to_string())));
+    assert_eq!(queue.values(), vec![-42]);
+}
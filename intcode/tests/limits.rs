@@ -0,0 +1,69 @@
+use intcode::{Computer, IntcodeErrorKind, Limit, LimitedRun, Program, RunLimits, RunState};
+
+use std::time::Duration;
+
+// JNZ #1, #0 forever
+const LOOP_FOREVER: &str = "1105,1,0";
+
+// ADD #1, #2 -> [100000000]; HALT
+const WRITE_FAR: &str = "1101,1,2,100000000,99";
+
+fn load<'a>(intcode: &str) -> Computer<'a>
+{
+    let mut computer = Computer::new();
+    computer.load_program(&Program::new_from_str(intcode.to_string()));
+    computer
+}
+
+#[test]
+fn instruction_budget_stops_an_endless_loop()
+{
+    let mut computer = load(LOOP_FOREVER);
+
+    assert_eq!(computer.run_with_limits(&RunLimits::new().with_max_instructions(1000)),
+               Ok(LimitedRun::LimitExceeded {limit: Limit::Instructions, instruction_pointer: 0, executed: 1000}));
+}
+
+#[test]
+fn program_within_its_budget_finishes()
+{
+    let mut computer = load("1101,1,2,0,99");
+
+    assert_eq!(computer.run_with_limits(&RunLimits::new().with_max_instructions(2)),
+               Ok(LimitedRun::Finished(RunState::Halted)));
+    assert_eq!(computer.get_value(0), 3);
+}
+
+#[test]
+fn limited_run_can_be_resumed()
+{
+    let mut computer = load("1101,1,2,0,1101,3,4,1,99");
+    let limits = RunLimits::new().with_max_instructions(1);
+
+    assert_eq!(computer.run_with_limits(&limits),
+               Ok(LimitedRun::LimitExceeded {limit: Limit::Instructions, instruction_pointer: 4, executed: 1}));
+    assert_eq!(computer.run_with_limits(&limits),
+               Ok(LimitedRun::LimitExceeded {limit: Limit::Instructions, instruction_pointer: 8, executed: 1}));
+    assert_eq!(computer.run_with_limits(&limits), Ok(LimitedRun::Finished(RunState::Halted)));
+    assert_eq!((computer.get_value(0), computer.get_value(1)), (3, 7));
+}
+
+#[test]
+fn passed_deadline_stops_the_program()
+{
+    let mut computer = load(LOOP_FOREVER);
+
+    assert_eq!(computer.run_with_limits(&RunLimits::new().with_timeout(Duration::ZERO)),
+               Ok(LimitedRun::LimitExceeded {limit: Limit::Deadline, instruction_pointer: 0, executed: 0}));
+}
+
+#[test]
+fn memory_budget_stops_a_limited_run()
+{
+    let mut computer = load(WRITE_FAR);
+    computer.set_memory_limit(1024);
+
+    let error = computer.run_with_limits(&RunLimits::new().with_max_instructions(1000)).unwrap_err();
+    assert_eq!(error.kind, IntcodeErrorKind::MemoryLimitExceeded(100_000_000));
+    assert_eq!(error.instruction_pointer, 0);
+}
@@ -0,0 +1,61 @@
+use intcode::{Computer, IntcodeErrorKind, Program, RunState};
+
+// OUT [1000000000]; HALT
+const READ_FAR: &str = "4,1000000000,99";
+
+// ADD #1, #2 -> [100000000]; HALT
+const WRITE_FAR: &str = "1101,1,2,100000000,99";
+
+#[test]
+fn reads_past_the_end_are_zero()
+{
+    let program = Program::new_from_str(READ_FAR.to_string());
+    assert_eq!(program.get(3), 0);
+    assert_eq!(program.get(usize::MAX), 0);
+
+    let mut computer = Computer::new();
+    computer.load_program(&program);
+    let outputs: Vec<i64> = computer.into_outputs(vec![]).map(Result::unwrap).collect();
+    assert_eq!(outputs, vec![0]);
+}
+
+#[test]
+fn far_write_allocates_a_single_page()
+{
+    let mut program = Program::new_from_str(READ_FAR.to_string());
+    program[1_000_000_000] = 7;
+
+    assert_eq!(program.get(1_000_000_000), 7);
+    assert_eq!(program.len(), 1_000_000_001);
+    assert!(program.allocated() <= 2048, "{} values allocated", program.allocated());
+}
+
+#[test]
+fn writes_over_the_memory_limit_stop_the_program()
+{
+    let mut computer = Computer::new();
+    computer.set_memory_limit(0);
+    computer.load_program(&Program::new_from_str(WRITE_FAR.to_string()));
+
+    let error = computer.run().unwrap_err();
+    assert_eq!(error.kind, IntcodeErrorKind::MemoryLimitExceeded(100_000_000));
+    assert_eq!(error.instruction_pointer, 0);
+
+    // the same write fits without the limit
+    computer.set_memory_limit(1024 * 1024);
+    assert_eq!(computer.run(), Ok(RunState::Halted));
+    assert_eq!(computer.get_value(100_000_000), 3);
+}
+
+#[test]
+fn addresses_past_i64_are_out_of_range()
+{
+    let mut computer = Computer::new();
+    computer.load_program(&Program::new_from_str("99".to_string()));
+
+    assert_eq!(computer.try_set_value(usize::MAX, 1).unwrap_err().kind, IntcodeErrorKind::AddressOutOfRange);
+    assert_eq!(computer.try_set_value(1 << 40, 1), Ok(()));
+
+    computer.set_memory_limit(0);
+    assert_eq!(computer.try_set_value(1 << 41, 1).unwrap_err().kind, IntcodeErrorKind::MemoryLimitExceeded(1 << 41));
+}
@@ -0,0 +1,56 @@
+use intcode::{Network, NetworkError, NetworkState, Program, Topology};
+
+// IN x; ADD x, 1 -> x; LT x, 10 -> c; JZ c, halt; OUT x; JMP 0
+const PASS_TOKEN: &str = "3,100,1001,100,1,100,1007,100,10,101,1006,101,18,4,100,1105,1,0,99";
+
+// IN x; ADD x, 1 -> x; OUT x; HALT
+const ADD_ONE: &str = "3,9,1001,9,1,9,4,9,99,0";
+
+#[test]
+fn ring_reports_deadlock_once_machine_0_halts()
+{
+    let program = Program::new_from_str(PASS_TOKEN.to_string());
+    let mut network = Network::from_program(&program, 3, Topology::Ring).unwrap();
+
+    network.send(0, 0);
+
+    // every machine loops back to its IN, the token goes round until machine 0 reaches 10
+    assert_eq!(network.run(), Ok(NetworkState::Deadlocked(vec![1, 2])));
+    assert!(network.machine(0).is_halted());
+    assert_eq!(network.last_output(2), Some(9));
+}
+
+#[test]
+fn chain_passes_values_down_and_out_of_the_last_machine()
+{
+    let program = Program::new_from_str(ADD_ONE.to_string());
+    let mut network = Network::from_program(&program, 3, Topology::Chain).unwrap();
+
+    network.send(0, 0);
+
+    assert_eq!(network.run(), Ok(NetworkState::Halted));
+    assert_eq!(network.take_outputs(2), vec![3]);
+    // outputs routed to another machine don't leave the network
+    assert_eq!(network.take_outputs(0), vec![]);
+    assert_eq!(network.last_output(0), Some(1));
+}
+
+#[test]
+fn ring_without_input_is_deadlocked()
+{
+    let program = Program::new_from_str(ADD_ONE.to_string());
+    let mut network = Network::from_program(&program, 2, Topology::Ring).unwrap();
+
+    assert_eq!(network.run(), Ok(NetworkState::Deadlocked(vec![0, 1])));
+    assert!(!network.machine(0).is_halted());
+    assert!(!network.machine(1).is_halted());
+}
+
+#[test]
+fn graph_edge_to_a_missing_machine_is_an_error()
+{
+    let program = Program::new_from_str(ADD_ONE.to_string());
+    let network = Network::from_program(&program, 2, Topology::Graph(vec![(0, 1), (1, 2)]));
+
+    assert_eq!(network.err(), Some(NetworkError::InvalidEdge {from: 1, to: 2}));
+}
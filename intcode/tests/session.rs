@@ -0,0 +1,111 @@
+use intcode::{Computer, IOQueue, Program, ReplayError, RunState, Session, SessionEvent};
+
+// IN a; IN b; OUT a + b; OUT a * b
+const SUM_AND_PRODUCT: &str = "3,20,3,21,1,20,21,22,4,22,2,20,21,22,4,22,99,0,0,0,0,0,0";
+
+fn computer(input: &IOQueue) -> Computer<'static>
+{
+    let mut computer = Computer::new();
+    computer.load_program(&Program::new_from_str(SUM_AND_PRODUCT.to_string()));
+    computer.set_input_queue(input);
+    computer.set_output(|_| ());
+    computer
+}
+
+#[test]
+fn recorded_session_replays_after_a_round_trip()
+{
+    let input = IOQueue::new();
+    input.push_back(3).unwrap();
+    input.push_back(4).unwrap();
+
+    let mut computer = computer(&input);
+    computer.set_session_recorder(Session::new());
+    assert_eq!(computer.run(), Ok(RunState::Halted));
+    let session = computer.take_session_recorder().unwrap();
+
+    assert_eq!(session.events(), &[
+        SessionEvent::Input {step: 0, value: 3},
+        SessionEvent::Input {step: 1, value: 4},
+        SessionEvent::Output {step: 3, value: 7},
+        SessionEvent::Output {step: 5, value: 12}
+    ]);
+
+    let mut saved = Vec::new();
+    session.write_to(&mut saved).unwrap();
+    let loaded = Session::read_from(&saved[..]).unwrap();
+
+    assert_eq!(loaded, session);
+    assert_eq!(loaded.replay(&Program::new_from_str(SUM_AND_PRODUCT.to_string())), Ok(()));
+}
+
+#[test]
+fn replay_reports_a_different_output()
+{
+    let text = "intcode-session 1\ninput 0 3\ninput 1 4\noutput 3 8\n";
+    let session: Session = Session::read_from(text.as_bytes()).unwrap();
+
+    assert_eq!(session.replay(&Program::new_from_str(SUM_AND_PRODUCT.to_string())),
+               Err(ReplayError::OutputMismatch {index: 0, expected: (3, 8), actual: (3, 7)}));
+}
+
+#[test]
+fn session_started_mid_run_replays_from_a_snapshot()
+{
+    let input = IOQueue::new();
+    input.push_back(3).unwrap();
+    input.push_back(4).unwrap();
+
+    let mut computer = computer(&input);
+    assert_eq!(computer.run_until_output(), Ok(RunState::ProducedOutput(7)));
+
+    let snapshot = computer.snapshot();
+    computer.set_session_recorder(Session::new());
+    assert_eq!(computer.run(), Ok(RunState::Halted));
+    let session = computer.take_session_recorder().unwrap();
+
+    assert_eq!(session.events(), &[SessionEvent::Output {step: 1, value: 12}]);
+    assert_eq!(session.replay_from(&snapshot), Ok(()));
+}
+
+#[test]
+fn stepping_back_only_forgets_the_undone_step()
+{
+    let input = IOQueue::new();
+    input.push_back(3).unwrap();
+    input.push_back(4).unwrap();
+
+    let mut computer = computer(&input);
+    computer.record_history(10);
+    computer.set_session_recorder(Session::new());
+    assert_eq!(computer.run_until_output(), Ok(RunState::ProducedOutput(7)));
+    assert_eq!(computer.step(), Ok(RunState::Running));
+
+    // undoing the MUL leaves the output before it alone
+    assert!(computer.step_back());
+    assert_eq!(computer.take_session_recorder().unwrap().events().len(), 3);
+}
+
+#[test]
+fn stepping_back_past_the_recording_start_keeps_steps_aligned()
+{
+    let input = IOQueue::new();
+    input.push_back(3).unwrap();
+    input.push_back(4).unwrap();
+
+    let mut computer = computer(&input);
+    computer.record_history(10);
+    assert_eq!(computer.step(), Ok(RunState::Running));
+
+    computer.set_session_recorder(Session::new());
+    assert_eq!(computer.run_until_output(), Ok(RunState::ProducedOutput(7)));
+
+    // the first input was read before the recording started and isn't recorded again
+    while computer.step_back() {}
+    assert_eq!(computer.run_until_output(), Ok(RunState::ProducedOutput(7)));
+
+    assert_eq!(computer.take_session_recorder().unwrap().events(), &[
+        SessionEvent::Input {step: 0, value: 4},
+        SessionEvent::Output {step: 2, value: 7}
+    ]);
+}
@@ -0,0 +1,68 @@
+use intcode::{Computer, IOQueue, Program, RunState, Snapshot, SnapshotError};
+
+// IN a; IN b; OUT a + b; OUT a * b
+const SUM_AND_PRODUCT: &str = "3,20,3,21,1,20,21,22,4,22,2,20,21,22,4,22,99,0,0,0,0,0,0";
+
+fn sum_and_product<'a>(input: &IOQueue, output: &IOQueue) -> Computer<'a>
+{
+    let mut computer = Computer::new();
+    computer.load_program(&Program::new_from_str(SUM_AND_PRODUCT.to_string()));
+    computer.set_input_queue(input);
+    computer.set_output_queue(output);
+    computer
+}
+
+#[test]
+fn restored_computer_carries_on_where_the_snapshot_was_taken()
+{
+    let (input, output) = (IOQueue::new(), IOQueue::new());
+    input.push_back(3).unwrap();
+
+    let mut computer = sum_and_product(&input, &output);
+    assert_eq!(computer.run(), Ok(RunState::AwaitingInput));
+    input.push_back(4).unwrap();
+
+    let mut saved = Vec::new();
+    computer.snapshot().write_to(&mut saved).unwrap();
+    let snapshot = Snapshot::read_from(&saved[..]).unwrap();
+    assert_eq!(snapshot, computer.snapshot());
+
+    // the restored computer gets the pending input back in its own queues
+    let (restored_input, restored_output) = (IOQueue::new(), IOQueue::new());
+    let mut restored = sum_and_product(&restored_input, &restored_output);
+    restored.restore(&snapshot);
+
+    assert_eq!(restored.instruction_pointer(), 2);
+    assert_eq!(restored_input.values(), vec![4]);
+    assert_eq!(restored.run(), Ok(RunState::Halted));
+    assert_eq!(restored_output.values(), vec![7, 12]);
+}
+
+#[test]
+fn memory_far_past_the_program_survives_a_round_trip()
+{
+    let mut computer = Computer::new();
+    computer.load_program(&Program::new_from_str("99".to_string()));
+    computer.set_value(1_000_000_000, 42);
+
+    let mut saved = Vec::new();
+    computer.snapshot().write_to(&mut saved).unwrap();
+    assert!(String::from_utf8(saved.clone()).unwrap().contains("\nsegment 999999488 "));
+
+    let mut restored = Computer::new();
+    restored.restore(&Snapshot::read_from(&saved[..]).unwrap());
+    assert_eq!(restored.get_value(1_000_000_000), 42);
+    assert_eq!(restored.get_value(999_999_999), 0);
+}
+
+#[test]
+fn snapshot_of_another_version_is_rejected()
+{
+    let text = "intcode-snapshot 1\ninstruction_pointer 0\nrelative_base_offset 0\nmemory 99\n";
+
+    match Snapshot::<i64>::read_from(text.as_bytes())
+    {
+        Err(SnapshotError::UnsupportedVersion(version)) => assert_eq!(version, "1"),
+        other => panic!("expected an unsupported version, got {:?}", other)
+    }
+}
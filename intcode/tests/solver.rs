@@ -0,0 +1,54 @@
+use intcode::{Program, Solution, SolveMethod, Solver};
+
+use std::time::{Duration, Instant};
+
+// ADD [5], [6] -> [0]; HALT with the inputs at 5 and 6
+const SUM: &str = "1,5,6,0,99,0,0";
+
+// MUL [5], [6] -> [0]; HALT with the inputs at 5 and 6
+const PRODUCT: &str = "2,5,6,0,99,0,0";
+
+fn solver(intcode: &str) -> Solver
+{
+    Solver::new(&Program::new_from_str(intcode.to_string()), 0)
+}
+
+#[test]
+fn linear_output_is_solved_directly()
+{
+    let solver = solver(SUM).with_input(5, 0..=9).with_input(6, 0..=9);
+
+    // 0 + 10 is out of range, so 1 + 9 comes first
+    assert_eq!(solver.solve(10), Some(Solution {values: vec![1, 9], method: SolveMethod::Linear}));
+    assert_eq!(solver.solve(19), None);
+}
+
+#[test]
+fn wide_linear_range_is_solved_without_trying_every_value()
+{
+    let solver = solver(SUM).with_input(5, 500_000_000..=1_000_000_000).with_input(6, 500_000_000..=1_000_000_000);
+
+    let start = Instant::now();
+    assert_eq!(solver.solve(1_999_999_999),
+               Some(Solution {values: vec![999_999_999, 1_000_000_000], method: SolveMethod::Linear}));
+    assert!(start.elapsed() < Duration::from_secs(1), "took {:?}", start.elapsed());
+}
+
+#[test]
+fn nonlinear_output_falls_back_to_brute_force()
+{
+    let solver = solver(PRODUCT).with_input(5, 1..=10).with_input(6, 1..=10);
+
+    // 1 * 12 is out of range, so 2 * 6 comes first
+    assert_eq!(solver.solve(12), Some(Solution {values: vec![2, 6], method: SolveMethod::BruteForce}));
+    assert_eq!(solver.solve(11), None);
+}
+
+#[test]
+fn search_gives_up_past_max_combinations()
+{
+    let solver = solver(PRODUCT).with_input(5, 0..=99).with_input(6, 0..=99);
+
+    assert_eq!(solver.solve(99 * 99).map(|solution| solution.values), Some(vec![99, 99]));
+    assert_eq!(solver.with_max_combinations(100).solve(99 * 99), None);
+}